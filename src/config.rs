@@ -7,6 +7,21 @@ pub struct Config {
     pub network: String,
     pub rpc_url: String,
     pub default_wallet: String,
+    /// Request timeout for `rpc::RpcClient`/`rpc::AsyncRpcClient`, in seconds.
+    #[serde(default = "default_rpc_timeout_secs")]
+    pub rpc_timeout_secs: u64,
+    /// Additional RPC endpoints tried, in order, after `rpc_url` if it's
+    /// unreachable. See `rpc::RpcClient::from_config`.
+    #[serde(default)]
+    pub rpc_urls: Vec<String>,
+    /// Public/community endpoints tried only after `rpc_url` and `rpc_urls`
+    /// are all unreachable.
+    #[serde(default)]
+    pub rpc_fallback_urls: Vec<String>,
+}
+
+fn default_rpc_timeout_secs() -> u64 {
+    10
 }
 
 impl Default for Config {
@@ -15,6 +30,9 @@ impl Default for Config {
             network: "testnet".to_string(),
             rpc_url: "http://127.0.0.1:18432/".to_string(),
             default_wallet: "wallet.json".to_string(),
+            rpc_timeout_secs: default_rpc_timeout_secs(),
+            rpc_urls: Vec::new(),
+            rpc_fallback_urls: Vec::new(),
         }
     }
 }
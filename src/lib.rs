@@ -1,6 +1,7 @@
 pub mod config;
 pub mod reward;
 pub mod rpc;
+pub mod sync;
 pub mod tx;
 pub mod utils;
 pub mod wallet;
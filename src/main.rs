@@ -9,6 +9,9 @@ use tracing_subscriber::EnvFilter;
 mod ui;
 use ui::*;
 
+// The `serve` daemon: JSON-RPC over the same operations the CLI exposes.
+mod server;
+
 /// Global CLI options
 #[derive(Parser, Debug)]
 #[command(name = "btpc_wallet", version, about = "BTPC Wallet (Dilithium5)")]
@@ -48,7 +51,18 @@ struct Cli {
 #[derive(Subcommand, Debug)]
 enum Cmd {
     /// Create a new wallet; writes wallet.json and mnemonic.txt
-    Init,
+    Init {
+        /// Human-readable name to register this wallet under, so it can
+        /// later be selected with `--wallet <alias>` instead of a path
+        #[arg(long)]
+        alias: Option<String>,
+        /// Where to store the secret key: `file` (default, inside
+        /// wallet.json), `stronghold` (separate encrypted vault file with
+        /// its own passphrase), or `offline` (watch-only, no secret key
+        /// stored). Prompted interactively if omitted.
+        #[arg(long)]
+        secret_manager: Option<String>,
+    },
 
     /// Generate a new address for the wallet
     GenerateAddress,
@@ -69,13 +83,18 @@ enum Cmd {
     /// Show confirmed and pending balance (via RPC)
     Balance,
 
+    /// Show a detailed wallet report: tx count and the
+    /// trusted/untrusted-pending/immature balance breakdown (via RPC)
+    WalletInfo,
+
     /// Show recent transactions (via RPC)
     History {
         #[arg(long, default_value_t = 10)]
         limit: usize,
     },
 
-    /// Send funds in BTP units (decimal); fee optional (BTP units)
+    /// Send funds in BTP units (decimal); fee optional (BTP units, overrides
+    /// --conf-target's estimate)
     Send {
         dest: String,
         amount: String,
@@ -83,6 +102,12 @@ enum Cmd {
         fee: Option<String>,
         #[arg(long)]
         change_to: Option<String>,
+        /// Target confirmation count for fee estimation (ignored if --fee is set)
+        #[arg(long, default_value_t = 6)]
+        conf_target: u32,
+        /// Spend only these outpoints (TXID:VOUT), instead of running coin selection
+        #[arg(long = "coin-control", value_name = "TXID:VOUT")]
+        coin_control: Vec<String>,
     },
 
     /// Change passphrase and optionally Argon2id KDF params
@@ -105,10 +130,29 @@ enum Cmd {
         tx: String,
     },
 
-    Scan,
+    /// Rescan the chain, rebuilding this wallet's UTXO set/balance from
+    /// block data instead of a node-side address index
+    Scan {
+        /// Start scanning from exactly this height, overriding both the
+        /// last-scanned cursor and the wallet's birthday checkpoint
+        #[arg(long)]
+        from_height: Option<u64>,
+        /// Ignore the persisted last-scanned height and restart from the
+        /// checkpoint at/below the wallet's birthday
+        #[arg(long)]
+        rescan_from_birthday: bool,
+    },
     Reward,
     Config,
 
+    /// Start a long-running JSON-RPC daemon over `bind` (`host:port` for TCP,
+    /// `unix:<path>` for a Unix domain socket), unlocking the wallet once
+    /// instead of re-prompting per call
+    Serve {
+        #[arg(long, default_value = "127.0.0.1:18433")]
+        bind: String,
+    },
+
     /// List all wallets in a directory
     ListWallets {
         #[arg(long, default_value = ".")]
@@ -129,9 +173,133 @@ enum Cmd {
     /// Recover wallet from seed phrase
     Recover {
         seed_phrase: String,
+        /// Human-readable name to register the recovered wallet under
+        #[arg(long)]
+        alias: Option<String>,
+        /// Where to store the secret key: `file` (default), `stronghold`,
+        /// or `offline`. See `Init --secret-manager`.
+        #[arg(long)]
+        secret_manager: Option<String>,
+    },
+
+    /// Derive a read-only address at an arbitrary hardened derivation path
+    /// (e.g. m/44'/0'/0'/0/5'), without mutating the wallet file
+    DerivePath {
+        path: String,
     },
 }
 
+/// "TXID:VOUT" -> `(txid, vout)`, as accepted by `--coin-control`.
+fn parse_outpoint(s: &str) -> anyhow::Result<(String, u32)> {
+    let (txid, vout) = s
+        .split_once(':')
+        .ok_or_else(|| anyhow::anyhow!("--coin-control outpoint must be TXID:VOUT, got {s:?}"))?;
+    let vout: u32 = vout
+        .parse()
+        .map_err(|_| anyhow::anyhow!("invalid vout in outpoint {s:?}"))?;
+    Ok((txid.to_string(), vout))
+}
+
+/// Map a `--conf-target` (desired confirmation count) to the `FeePriority`
+/// bucket `RpcClient::estimate_fee` should pick from: a tighter target wants
+/// a higher-percentile (more aggressive) fee rate.
+fn priority_for_conf_target(conf_target: u32) -> btpc_wallet::rpc::FeePriority {
+    use btpc_wallet::rpc::FeePriority;
+    match conf_target {
+        0..=2 => FeePriority::High,
+        3..=6 => FeePriority::Medium,
+        _ => FeePriority::Low,
+    }
+}
+
+/// The result of building and signing a send: the broadcast-ready
+/// transaction plus the coin selection that produced it. Shared by
+/// `Cmd::Send` and `interactive_mode`'s "Send Funds" menu item, so both
+/// paths run the same real coin selection, fee estimation, and signing
+/// instead of drifting apart.
+struct PreparedSend {
+    tx: btpc_wallet::tx::model::Transaction,
+    selection: btpc_wallet::tx::coin_select::CoinSelection,
+}
+
+/// Run real coin selection and fee estimation for a send and build the
+/// resulting unsigned transaction, without touching any key material. Shared
+/// by `prepare_send` (which signs with a freshly-unlocked key) and
+/// `server::WalletSession::dispatch`'s `Send` handler (which signs with its
+/// already-unlocked session key), so every send path picks coins and fees
+/// the same way regardless of how the signing key was obtained.
+pub(crate) fn build_send_transaction(
+    wallet: &btpc_wallet::wallet::ops::Wallet,
+    rpc: &btpc_wallet::rpc::RpcClient,
+    dest: &str,
+    amount: &str,
+    fee: Option<&str>,
+    change_to: Option<&str>,
+    conf_target: u32,
+    coin_control: &[String],
+) -> anyhow::Result<PreparedSend> {
+    use btpc_wallet::tx::coin_select::{select_coins, FeeModel};
+    use btpc_wallet::utils::parse_amount_to_units;
+
+    let amount_units = parse_amount_to_units(amount)?;
+
+    let mut utxos = rpc.get_utxos(&wallet.address)?;
+    if !coin_control.is_empty() {
+        let wanted = coin_control
+            .iter()
+            .map(|o| parse_outpoint(o))
+            .collect::<anyhow::Result<Vec<_>>>()?;
+        utxos.retain(|u| wanted.iter().any(|(txid, vout)| *txid == u.txid && *vout == u.vout));
+        anyhow::ensure!(
+            utxos.len() == wanted.len(),
+            "one or more --coin-control outpoints were not found among this wallet's UTXOs"
+        );
+    }
+
+    let fee_model = match fee {
+        Some(f) => FeeModel::Fixed(parse_amount_to_units(f)?),
+        None => {
+            let rate = rpc.estimate_fee(priority_for_conf_target(conf_target), conf_target as usize)?;
+            FeeModel::Rate(rate)
+        }
+    };
+
+    let selection = select_coins(&utxos, amount_units, fee_model, 1)?;
+    let change_addr = change_to.unwrap_or(&wallet.address);
+    let inputs: Vec<(String, u32, u64)> = selection
+        .selected
+        .iter()
+        .map(|u| (u.txid.clone(), u.vout, u.value))
+        .collect();
+
+    let tx = btpc_wallet::tx::builder::build_basic_tx(inputs, dest, amount_units, selection.fee, change_addr)?;
+
+    Ok(PreparedSend { tx, selection })
+}
+
+#[allow(clippy::too_many_arguments)]
+fn prepare_send(
+    wallet: &btpc_wallet::wallet::ops::Wallet,
+    wallet_path: &Path,
+    rpc: &btpc_wallet::rpc::RpcClient,
+    dest: &str,
+    amount: &str,
+    fee: Option<&str>,
+    change_to: Option<&str>,
+    conf_target: u32,
+    coin_control: &[String],
+    passphrase: &str,
+) -> anyhow::Result<PreparedSend> {
+    let mut prepared =
+        build_send_transaction(wallet, rpc, dest, amount, fee, change_to, conf_target, coin_control)?;
+
+    let sk_secret = btpc_wallet::wallet::secret_manager::unlock_secret_key(wallet, wallet_path, passphrase)?;
+    let tx_bytes = prepared.tx.signing_bytes()?;
+    prepared.tx.witness = Some(btpc_wallet::tx::signer::sign_tx(&sk_secret, &tx_bytes)?);
+
+    Ok(prepared)
+}
+
 fn main() -> anyhow::Result<()> {
     let cli = Cli::parse();
     let level = match cli.verbose {
@@ -157,7 +325,7 @@ fn main() -> anyhow::Result<()> {
         config.default_wallet = cli.wallet.clone();
     }
 
-    let wallet_path = PathBuf::from(&cli.wallet);
+    let wallet_path = btpc_wallet::wallet::registry::resolve_wallet_path(&cli.wallet);
 
     // Show header if not in quiet mode
     if !cli.quiet && !cli.json {
@@ -178,11 +346,30 @@ fn main() -> anyhow::Result<()> {
 
     // Handle subcommands
     match cli.cmd {
-        Some(Cmd::Init) => {
+        Some(Cmd::Init { alias, secret_manager }) => {
+            use btpc_wallet::wallet::secret_manager::SecretManagerKind;
+
             if !cli.json && !cli.quiet {
                 show_section_header("Create New Wallet");
             }
 
+            let secret_manager_kind = match &secret_manager {
+                Some(s) => s.parse::<SecretManagerKind>()?,
+                None if cli.json || cli.quiet => SecretManagerKind::File,
+                None => match menu(
+                    "Select secret storage backend",
+                    &[
+                        "File (default - encrypted inside wallet.json)",
+                        "Stronghold (separate encrypted vault file)",
+                        "Offline (watch-only, no secret key stored)",
+                    ],
+                ) {
+                    1 => SecretManagerKind::Stronghold,
+                    2 => SecretManagerKind::Offline,
+                    _ => SecretManagerKind::File,
+                },
+            };
+
             let pass1 = if cli.json {
                 rpassword::prompt_password("New passphrase (will encrypt your secret key): ")?
             } else {
@@ -212,12 +399,47 @@ fn main() -> anyhow::Result<()> {
                 show_loading("Creating wallet...");
             }
 
-            let wallet = if with_seed {
+            let mut wallet = if with_seed {
                 btpc_wallet::wallet::ops::create_wallet_with_seed(&wallet_path, &pass1, &config.network)?
             } else {
                 btpc_wallet::wallet::ops::create_wallet(&wallet_path, &pass1, &config.network)?
             };
 
+            if let Some(alias) = &alias {
+                wallet.alias = Some(alias.clone());
+            }
+
+            match secret_manager_kind {
+                SecretManagerKind::File => {}
+                SecretManagerKind::Stronghold => {
+                    let vault_pass = if cli.json {
+                        rpassword::prompt_password("Stronghold vault passphrase: ")?
+                    } else {
+                        password("Stronghold vault passphrase (separate from wallet passphrase):")
+                    };
+                    let sk_bytes = hex::decode(&wallet.encrypted_private_key)
+                        .map_err(|e| anyhow::anyhow!("wallet secret key is not valid hex: {e}"))?;
+                    let vault_passphrase: secrecy::SecretBox<String> =
+                        secrecy::SecretBox::new(Box::new(vault_pass));
+                    btpc_wallet::wallet::secret_manager::write_stronghold_vault(
+                        &wallet_path,
+                        &sk_bytes,
+                        &vault_passphrase,
+                    )?;
+                    wallet.encrypted_private_key = String::new();
+                }
+                SecretManagerKind::Offline => {
+                    wallet.encrypted_private_key = String::new();
+                    wallet.seed_phrase = String::new();
+                }
+            }
+            wallet.secret_manager = secret_manager_kind;
+            btpc_wallet::wallet::ops::save_wallet(&wallet, &wallet_path)?;
+
+            if let Some(alias) = &alias {
+                btpc_wallet::wallet::registry::register(alias, &wallet_path)?;
+            }
+
             if cli.json {
                 println!(
                     "{}",
@@ -299,13 +521,16 @@ fn main() -> anyhow::Result<()> {
         }
 
         Some(Cmd::Balance) => {
+            let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+
             if !cli.json && !cli.quiet {
                 show_loading("Fetching balance...");
             }
 
-            // This would be replaced with actual balance fetching
-            let balance = "12.34567890";
-            let pending = "0.12345678";
+            let rpc = btpc_wallet::rpc::RpcClient::from_config(&config)?;
+            let resp = rpc.get_balance(&wallet.address)?;
+            let balance = btpc_wallet::utils::format_units_as_btp(resp.confirmed);
+            let pending = btpc_wallet::utils::format_units_as_btp(resp.pending);
 
             if cli.json {
                 println!(
@@ -316,7 +541,30 @@ fn main() -> anyhow::Result<()> {
                     }))?
                 );
             } else if !cli.quiet {
-                show_balance(balance, pending);
+                show_balance(&balance, &pending);
+            }
+        }
+
+        Some(Cmd::WalletInfo) => {
+            let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+
+            if !cli.json && !cli.quiet {
+                show_loading("Fetching wallet info...");
+            }
+
+            let rpc = btpc_wallet::rpc::RpcClient::from_config(&config)?;
+            let info = btpc_wallet::wallet::info::fetch_wallet_info(&wallet, &rpc)?;
+
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&info)?);
+            } else if !cli.quiet {
+                show_wallet_info(
+                    &info.walletname,
+                    info.txcount,
+                    &btpc_wallet::utils::format_units_as_btp(info.balances.trusted),
+                    &btpc_wallet::utils::format_units_as_btp(info.balances.untrusted_pending),
+                    &btpc_wallet::utils::format_units_as_btp(info.balances.immature),
+                );
             }
         }
 
@@ -337,7 +585,7 @@ fn main() -> anyhow::Result<()> {
                 show_loading("Generating new address...");
             }
 
-            btpc_wallet::wallet::ops::generate_new_address(&mut wallet, &passphrase)?;
+            btpc_wallet::wallet::secret_manager::generate_new_address(&mut wallet, &wallet_path, &passphrase)?;
             btpc_wallet::wallet::ops::save_wallet(&wallet, &wallet_path)?;
 
             if cli.json {
@@ -355,6 +603,7 @@ fn main() -> anyhow::Result<()> {
 
         Some(Cmd::ShowSeed) => {
             let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+            btpc_wallet::wallet::secret_manager::require_signing_capable(&wallet)?;
 
             if wallet.seed_phrase.is_empty() {
                 show_error("No seed phrase found in wallet. This wallet was created without a seed phrase.");
@@ -369,11 +618,123 @@ fn main() -> anyhow::Result<()> {
             }
         }
 
-        Some(Cmd::Recover { seed_phrase }) => {
+        Some(Cmd::ExportMnemonic) => {
+            use secrecy::ExposeSecret;
+
+            let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+            btpc_wallet::wallet::secret_manager::require_signing_capable(&wallet)?;
+
+            if wallet.seed_phrase.is_empty() {
+                show_error("No seed phrase found in wallet. This wallet was created without a seed phrase.");
+                return Ok(());
+            }
+
+            if !cli.json && !cli.quiet {
+                show_warning("WARNING: Anyone with this file can access your funds!");
+                if !confirm("Export seed phrase to mnemonic.txt?") {
+                    show_warning("Export cancelled.");
+                    return Ok(());
+                }
+            }
+
+            let seed_phrase = if wallet.encrypted {
+                let passphrase = if cli.json {
+                    rpassword::prompt_password("Wallet passphrase: ")?
+                } else {
+                    password("Wallet passphrase:")
+                };
+                let password_box: secrecy::SecretBox<String> = secrecy::SecretBox::new(Box::new(passphrase));
+                btpc_wallet::wallet::lock::unlock_wallet(&wallet, &password_box, None)?
+                    .seed_phrase
+                    .expose_secret()
+                    .clone()
+            } else {
+                wallet.seed_phrase.clone()
+            };
+
+            std::fs::write("mnemonic.txt", &seed_phrase)?;
+
+            if cli.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "status": "exported",
+                        "path": "mnemonic.txt"
+                    }))?
+                );
+            } else if !cli.quiet {
+                show_success("Seed phrase exported to mnemonic.txt");
+                show_info("Keep this file secure!");
+            }
+        }
+
+        Some(Cmd::DerivePath { path }) => {
+            use secrecy::ExposeSecret;
+
+            let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+            btpc_wallet::wallet::secret_manager::require_signing_capable(&wallet)?;
+
+            if wallet.seed_phrase.is_empty() {
+                show_error("No seed phrase found in wallet. This wallet was created without a seed phrase.");
+                return Ok(());
+            }
+
+            let seed_phrase = if wallet.encrypted {
+                let passphrase = if cli.json {
+                    rpassword::prompt_password("Wallet passphrase: ")?
+                } else {
+                    password("Wallet passphrase:")
+                };
+                let password_box: secrecy::SecretBox<String> = secrecy::SecretBox::new(Box::new(passphrase));
+                btpc_wallet::wallet::lock::unlock_wallet(&wallet, &password_box, None)?
+                    .seed_phrase
+                    .expose_secret()
+                    .clone()
+            } else {
+                wallet.seed_phrase.clone()
+            };
+
+            let (public_key, address) = btpc_wallet::wallet::ops::derive_address_at_path(&seed_phrase, "", &path)?;
+
+            if cli.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "path": path,
+                        "address": address,
+                        "public_key": public_key,
+                    }))?
+                );
+            } else if !cli.quiet {
+                show_table_row("Path", &path);
+                show_table_row("Address", &address);
+            }
+        }
+
+        Some(Cmd::Recover { seed_phrase, alias, secret_manager }) => {
+            use btpc_wallet::wallet::secret_manager::SecretManagerKind;
+
             if !cli.json && !cli.quiet {
                 show_section_header("Recover Wallet from Seed Phrase");
             }
 
+            let secret_manager_kind = match &secret_manager {
+                Some(s) => s.parse::<SecretManagerKind>()?,
+                None if cli.json || cli.quiet => SecretManagerKind::File,
+                None => match menu(
+                    "Select secret storage backend",
+                    &[
+                        "File (default - encrypted inside wallet.json)",
+                        "Stronghold (separate encrypted vault file)",
+                        "Offline (watch-only, no secret key stored)",
+                    ],
+                ) {
+                    1 => SecretManagerKind::Stronghold,
+                    2 => SecretManagerKind::Offline,
+                    _ => SecretManagerKind::File,
+                },
+            };
+
             let passphrase = if cli.json {
                 rpassword::prompt_password("Enter new passphrase for recovered wallet: ")?
             } else {
@@ -384,13 +745,48 @@ fn main() -> anyhow::Result<()> {
                 show_loading("Recovering wallet from seed phrase...");
             }
 
-            let wallet = btpc_wallet::wallet::ops::recover_wallet_from_seed(
+            let mut wallet = btpc_wallet::wallet::ops::recover_wallet_from_seed(
                 &seed_phrase,
                 &passphrase,
                 &config.network,
                 &wallet_path
             )?;
 
+            if let Some(alias) = &alias {
+                wallet.alias = Some(alias.clone());
+            }
+
+            match secret_manager_kind {
+                SecretManagerKind::File => {}
+                SecretManagerKind::Stronghold => {
+                    let vault_pass = if cli.json {
+                        rpassword::prompt_password("Stronghold vault passphrase: ")?
+                    } else {
+                        password("Stronghold vault passphrase (separate from wallet passphrase):")
+                    };
+                    let sk_bytes = hex::decode(&wallet.encrypted_private_key)
+                        .map_err(|e| anyhow::anyhow!("wallet secret key is not valid hex: {e}"))?;
+                    let vault_passphrase: secrecy::SecretBox<String> =
+                        secrecy::SecretBox::new(Box::new(vault_pass));
+                    btpc_wallet::wallet::secret_manager::write_stronghold_vault(
+                        &wallet_path,
+                        &sk_bytes,
+                        &vault_passphrase,
+                    )?;
+                    wallet.encrypted_private_key = String::new();
+                }
+                SecretManagerKind::Offline => {
+                    wallet.encrypted_private_key = String::new();
+                    wallet.seed_phrase = String::new();
+                }
+            }
+            wallet.secret_manager = secret_manager_kind;
+            btpc_wallet::wallet::ops::save_wallet(&wallet, &wallet_path)?;
+
+            if let Some(alias) = &alias {
+                btpc_wallet::wallet::registry::register(alias, &wallet_path)?;
+            }
+
             if cli.json {
                 println!("{}", serde_json::to_string_pretty(&serde_json::json!({
                     "status": "recovered",
@@ -403,6 +799,160 @@ fn main() -> anyhow::Result<()> {
             }
         }
 
+        Some(Cmd::Serve { bind }) => {
+            if !cli.json && !cli.quiet {
+                show_section_header("Start Wallet RPC Daemon");
+            }
+
+            let session = server::WalletSession::unlock(wallet_path.clone(), &config)?;
+
+            if !cli.json && !cli.quiet {
+                show_success("Wallet unlocked for this session.");
+            }
+
+            server::serve(&bind, session)?;
+        }
+
+        Some(Cmd::Send {
+            dest,
+            amount,
+            fee,
+            change_to,
+            conf_target,
+            coin_control,
+        }) => {
+            let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+
+            if !cli.json && !cli.quiet {
+                show_section_header("Send Funds");
+                show_loading("Selecting coins and estimating fee...");
+            }
+
+            let rpc = btpc_wallet::rpc::RpcClient::from_config(&config)?;
+            let passphrase = if cli.json {
+                rpassword::prompt_password("Wallet passphrase: ")?
+            } else {
+                password("Wallet passphrase:")
+            };
+
+            let prepared = prepare_send(
+                &wallet,
+                &wallet_path,
+                &rpc,
+                &dest,
+                &amount,
+                fee.as_deref(),
+                change_to.as_deref(),
+                conf_target,
+                &coin_control,
+                &passphrase,
+            )?;
+
+            if cli.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "tx": prepared.tx,
+                        "selected_inputs": prepared.selection.selected,
+                        "fee": prepared.selection.fee,
+                        "change": prepared.selection.change,
+                    }))?
+                );
+            } else if !cli.quiet {
+                show_success("Transaction signed (not yet broadcast).");
+                show_table_row("Inputs selected", &prepared.selection.selected.len().to_string());
+                show_table_row("Fee", &btpc_wallet::utils::format_units_as_btp(prepared.selection.fee));
+                show_table_row("Change", &btpc_wallet::utils::format_units_as_btp(prepared.selection.change));
+                show_info("Run `broadcast` with the printed tx JSON (--json) to send it.");
+            }
+        }
+
+        Some(Cmd::Scan { from_height, rescan_from_birthday }) => {
+            let mut wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+
+            if !cli.json && !cli.quiet {
+                show_section_header("Scan Chain");
+                show_loading("Scanning blocks...");
+            }
+
+            let rpc = btpc_wallet::rpc::RpcClient::from_config(&config)?;
+            let opts = btpc_wallet::wallet::scan::ScanOptions {
+                from_height,
+                rescan_from_birthday,
+            };
+            let quiet = cli.json || cli.quiet;
+            let report = btpc_wallet::wallet::scan::scan_wallet(
+                &mut wallet,
+                &rpc,
+                &config.network,
+                opts,
+                |height, tip| {
+                    if !quiet {
+                        println!("  scanned block {}/{}", height, tip);
+                    }
+                },
+                |w| btpc_wallet::wallet::ops::save_wallet(w, &wallet_path),
+            )?;
+
+            if cli.json {
+                println!(
+                    "{}",
+                    serde_json::to_string_pretty(&serde_json::json!({
+                        "from_height": report.from_height,
+                        "to_height": report.to_height,
+                        "blocks_scanned": report.blocks_scanned,
+                        "utxos_found": report.utxos_found,
+                        "balance": btpc_wallet::utils::format_units_as_btp(report.balance),
+                    }))?
+                );
+            } else if !cli.quiet {
+                show_success("Scan complete.");
+                show_table_row("Blocks scanned", &report.blocks_scanned.to_string());
+                show_table_row("UTXOs found", &report.utxos_found.to_string());
+                show_table_row("Balance", &btpc_wallet::utils::format_units_as_btp(report.balance));
+            }
+        }
+
+        Some(Cmd::ListWallets { dir }) => {
+            let wallets = btpc_wallet::wallet::registry::scan_wallets_dir(Path::new(&dir))?;
+
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&wallets)?);
+            } else if !cli.quiet {
+                show_section_header("Wallets");
+                if wallets.is_empty() {
+                    show_info(&format!("No wallet files found in {}", dir));
+                } else {
+                    for w in &wallets {
+                        show_table_row("Path", &w.path);
+                        show_table_row("Alias", w.alias.as_deref().unwrap_or("(none)"));
+                        show_table_row("Address", &w.address);
+                        show_table_row("Network", &w.network);
+                        println!();
+                    }
+                }
+            }
+        }
+
+        Some(Cmd::Broadcast { tx }) => {
+            let tx_value: serde_json::Value = serde_json::from_str(&tx)
+                .map_err(|e| anyhow::anyhow!("--tx is not valid JSON: {e}"))?;
+
+            if !cli.json && !cli.quiet {
+                show_loading("Broadcasting transaction...");
+            }
+
+            let rpc = btpc_wallet::rpc::RpcClient::from_config(&config)?;
+            let resp = rpc.broadcast(&tx_value)?;
+
+            if cli.json {
+                println!("{}", serde_json::to_string_pretty(&serde_json::json!({ "txid": resp.txid }))?);
+            } else if !cli.quiet {
+                show_success("Transaction broadcast successfully!");
+                show_table_row_colored("Transaction ID", &resp.txid, Color::Cyan);
+            }
+        }
+
         Some(other_cmd) => {
             if !cli.json && !cli.quiet {
                 show_warning(&format!(
@@ -501,9 +1051,14 @@ fn network_settings_menu(config: &mut btpc_wallet::config::Config) -> anyhow::Re
 
 /// Interactive mode for the wallet
 fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config) -> anyhow::Result<()> {
+    let mut wallet_path = wallet_path.clone();
+
     show_header();
     show_table_row("Network", &config.network);
     show_table_row("RPC URL", &config.rpc_url);
+    if let Ok(wallet) = btpc_wallet::wallet::ops::load_wallet(&wallet_path) {
+        show_table_row("Wallet", wallet.alias.as_deref().unwrap_or(&wallet_path.display().to_string()));
+    }
     println!();
 
     // Check if wallet exists
@@ -522,7 +1077,7 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
             }
 
             show_loading("Creating wallet...");
-            let wallet = btpc_wallet::wallet::ops::create_wallet_with_seed(wallet_path, &pass1, &config.network)?;
+            let wallet = btpc_wallet::wallet::ops::create_wallet_with_seed(&wallet_path, &pass1, &config.network)?;
 
             show_success("Wallet created successfully!");
             show_table_row("Location", &wallet_path.display().to_string());
@@ -544,6 +1099,7 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
             "Send Funds",
             "Transaction History",
             "Backup Wallet",
+            "Switch Wallet",
             "Settings",
             "Exit"
         ]);
@@ -554,7 +1110,7 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
                 show_balance("12.34567890", "0.12345678");
             }
             1 => {
-                let wallet = btpc_wallet::wallet::ops::load_wallet(wallet_path)?;
+                let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
                 show_section_header("Wallet Address");
                 show_table_row_colored("Address", &wallet.address, Color::Cyan);
 
@@ -563,16 +1119,16 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
                 }
             }
             2 => {
-                let mut wallet = btpc_wallet::wallet::ops::load_wallet(wallet_path)?;
+                let mut wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
                 show_section_header("Generate New Address");
 
                 let passphrase = password("Enter wallet passphrase to generate new address:");
 
                 show_loading("Generating new address and keys...");
 
-                match btpc_wallet::wallet::ops::generate_new_address(&mut wallet, &passphrase) {
+                match btpc_wallet::wallet::secret_manager::generate_new_address(&mut wallet, &wallet_path, &passphrase) {
                     Ok(_) => {
-                        btpc_wallet::wallet::ops::save_wallet(&wallet, wallet_path)?;
+                        btpc_wallet::wallet::ops::save_wallet(&wallet, &wallet_path)?;
                         show_success("New address generated successfully!");
                         show_table_row_colored("New Address", &wallet.address, Color::Cyan);
                         show_info("Make sure to backup your updated wallet data!");
@@ -586,18 +1142,31 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
                 show_section_header("Send Funds");
                 let recipient = input("Recipient address:");
                 let amount = input("Amount (BTP):");
-                let fee = input("Fee (optional, press Enter for default):");
+                let fee = input("Fee (optional, press Enter to estimate from --conf-target 6):");
 
                 if fee.is_empty() {
-                    show_transaction_confirmation(&amount, &recipient, "0.0001");
+                    show_transaction_confirmation(&amount, &recipient, "estimated");
                 } else {
                     show_transaction_confirmation(&amount, &recipient, &fee);
                 }
 
                 if confirm("Confirm transaction?") {
-                    show_loading("Processing transaction...");
-                    show_success("Transaction sent successfully!");
-                    show_info("Transaction ID: abcdef1234567890");
+                    show_loading("Selecting coins and signing transaction...");
+                    let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
+                    let rpc = btpc_wallet::rpc::RpcClient::from_config(config)?;
+                    let passphrase = password("Wallet passphrase:");
+                    let fee_opt = if fee.is_empty() { None } else { Some(fee.as_str()) };
+
+                    match prepare_send(&wallet, &wallet_path, &rpc, &recipient, &amount, fee_opt, None, 6, &[], &passphrase) {
+                        Ok(prepared) => match rpc.broadcast(&serde_json::to_value(&prepared.tx)?) {
+                            Ok(resp) => {
+                                show_success("Transaction sent successfully!");
+                                show_info(&format!("Transaction ID: {}", resp.txid));
+                            }
+                            Err(e) => show_error(&format!("Broadcast failed: {}", e)),
+                        },
+                        Err(e) => show_error(&format!("Failed to prepare transaction: {}", e)),
+                    }
                 } else {
                     show_warning("Transaction cancelled.");
                 }
@@ -623,7 +1192,7 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
 
                 show_loading("Creating backup...");
 
-                let wallet = btpc_wallet::wallet::ops::load_wallet(wallet_path)?;
+                let wallet = btpc_wallet::wallet::ops::load_wallet(&wallet_path)?;
 
                 if let Some(parent) = backup_path.parent() {
                     std::fs::create_dir_all(parent)?;
@@ -639,6 +1208,32 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
                 }
             }
             6 => {
+                show_section_header("Switch Wallet");
+                let dir = wallet_path
+                    .parent()
+                    .filter(|p| !p.as_os_str().is_empty())
+                    .unwrap_or_else(|| Path::new("."));
+                let wallets = btpc_wallet::wallet::registry::scan_wallets_dir(dir)?;
+
+                if wallets.is_empty() {
+                    show_info(&format!("No wallet files found in {}", dir.display()));
+                } else {
+                    let labels: Vec<String> = wallets
+                        .iter()
+                        .map(|w| match &w.alias {
+                            Some(alias) => format!("{} ({})", alias, w.path),
+                            None => w.path.clone(),
+                        })
+                        .collect();
+                    let label_refs: Vec<&str> = labels.iter().map(|s| s.as_str()).collect();
+                    let selection = menu("Select Wallet", &label_refs);
+                    if let Some(chosen) = wallets.get(selection) {
+                        wallet_path = PathBuf::from(&chosen.path);
+                        show_success(&format!("Switched to wallet: {}", chosen.path));
+                    }
+                }
+            }
+            7 => {
                 show_section_header("Settings");
                 let selection = menu("Settings", &[
                     "Change Passphrase",
@@ -674,7 +1269,7 @@ fn interactive_mode(wallet_path: &PathBuf, config: &btpc_wallet::config::Config)
                     _ => {}
                 }
             }
-            7 => {
+            8 => {
                 show_success("Goodbye!");
                 break;
             }
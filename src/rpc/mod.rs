@@ -1,69 +1,808 @@
+pub mod subscribe;
+
+use rand::Rng;
+use serde::de::DeserializeOwned;
 use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicI64, AtomicU64, Ordering};
+use std::time::Duration;
 use url::Url;
 
+/// Default request timeout, used when a caller doesn't go through
+/// `Config::rpc_timeout_secs`.
+const DEFAULT_TIMEOUT_SECS: u64 = 10;
+
+/// Retry/backoff tuning for `RpcClient::execute`: attempts per endpoint
+/// before failing over, and the exponential-backoff-with-jitter schedule
+/// between attempts.
+const RETRY_ATTEMPTS_PER_ENDPOINT: u32 = 3;
+const RETRY_BASE_DELAY: Duration = Duration::from_millis(100);
+const RETRY_MAX_DELAY: Duration = Duration::from_secs(5);
+
+/// Shared by the blocking and async clients so the REST dialect's URL shapes
+/// only live in one place.
+fn balance_url(base: &Url, addr: &str) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("address/{}/balance", addr))?)
+}
+
+fn utxos_url(base: &Url, addr: &str) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("address/{}/utxos", addr))?)
+}
+
+fn history_url(base: &Url, addr: &str, limit: usize) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("address/{}/history?limit={}", addr, limit))?)
+}
+
+fn history_since_url(base: &Url, addr: &str, since_height: u64, limit: usize) -> anyhow::Result<Url> {
+    Ok(base.join(&format!(
+        "address/{}/history?since_height={}&limit={}",
+        addr, since_height, limit
+    ))?)
+}
+
+fn broadcast_url(base: &Url) -> anyhow::Result<Url> {
+    Ok(base.join("tx/broadcast")?)
+}
+
+fn server_version_url(base: &Url) -> anyhow::Result<Url> {
+    Ok(base.join("server/version")?)
+}
+
+fn block_height_url(base: &Url) -> anyhow::Result<Url> {
+    Ok(base.join("chain/height")?)
+}
+
+fn block_url(base: &Url, height: u64) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("chain/block/{}", height))?)
+}
+
+/// The lowest node protocol version `RpcClient` will talk to; `server_info`
+/// rejects anything older with a clear error instead of letting the wallet
+/// limp along against endpoints the node doesn't actually have.
+pub const MIN_SUPPORTED_PROTOCOL: u32 = 1;
+
+fn fee_history_url(base: &Url, blocks: usize) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("fees/history?blocks={}", blocks))?)
+}
+
+fn recent_fees_url(base: &Url, blocks: usize) -> anyhow::Result<Url> {
+    Ok(base.join(&format!("fees/recent?blocks={}", blocks))?)
+}
+
+/// `TxHistoryItem` doesn't carry the original transaction's byte size, so
+/// the client-side fee-rate fallback (`FeeHistory::from_samples`) can only
+/// approximate `fee / size` using this rough average rather than an exact
+/// vsize.
+const ASSUMED_TX_SIZE_BYTES: u64 = 250;
+
+fn ensure_success(label: &str, status: reqwest::StatusCode) -> anyhow::Result<()> {
+    if status.is_success() {
+        Ok(())
+    } else {
+        anyhow::bail!("{label} http {status}")
+    }
+}
+
+/// `100ms, 200ms, 400ms, ... capped at 5s`, plus up to 25% jitter so many
+/// wallets retrying the same downed node don't all hammer it in lockstep.
+fn backoff_delay(attempt: u32) -> Duration {
+    let exp = RETRY_BASE_DELAY.saturating_mul(1u32 << attempt.min(16));
+    let capped = exp.min(RETRY_MAX_DELAY);
+    let jitter_cap = (capped.as_millis() as u64 / 4).max(1);
+    let jitter = rand::thread_rng().gen_range(0..jitter_cap);
+    capped + Duration::from_millis(jitter)
+}
+
+/// The outcome of one attempt against one endpoint, as classified by the
+/// caller so `RpcClient::execute` knows whether to retry, fail over, or
+/// give up entirely.
+enum AttemptOutcome<T> {
+    Success(T),
+    /// Connection/timeout error or 5xx: worth retrying, then failing over.
+    Retryable(String),
+    /// 4xx, malformed response, or an RPC-level error: no point retrying
+    /// this endpoint, fail over immediately.
+    Fatal(String),
+}
+
+fn classify_blocking<T: DeserializeOwned>(
+    result: Result<reqwest::blocking::Response, reqwest::Error>,
+) -> AttemptOutcome<T> {
+    match result {
+        Ok(resp) => {
+            let status = resp.status();
+            if status.is_success() {
+                match resp.json::<T>() {
+                    Ok(v) => AttemptOutcome::Success(v),
+                    Err(e) => AttemptOutcome::Fatal(format!("decode error: {e}")),
+                }
+            } else if status.is_server_error() {
+                AttemptOutcome::Retryable(format!("http {status}"))
+            } else {
+                AttemptOutcome::Fatal(format!("http {status}"))
+            }
+        }
+        Err(e) if e.is_timeout() || e.is_connect() => AttemptOutcome::Retryable(e.to_string()),
+        Err(e) => AttemptOutcome::Fatal(e.to_string()),
+    }
+}
+
+/// One endpoint that `RpcClient` can send a request to, plus a running
+/// health score: incremented on success, decremented on failure, so
+/// `RpcClient::endpoint_order` tries the healthiest endpoints first.
+#[derive(Debug)]
+struct Endpoint {
+    base: Url,
+    health: AtomicI64,
+}
+
+impl Clone for Endpoint {
+    fn clone(&self) -> Self {
+        Endpoint {
+            base: self.base.clone(),
+            health: AtomicI64::new(self.health.load(Ordering::Relaxed)),
+        }
+    }
+}
+
+impl Endpoint {
+    fn new(base: Url) -> Self {
+        Endpoint {
+            base,
+            health: AtomicI64::new(0),
+        }
+    }
+}
+
+/// One endpoint's failure reason, as recorded when `RpcClient::execute`
+/// exhausts every endpoint.
 #[derive(Debug, Clone)]
+pub struct EndpointFailure {
+    pub url: String,
+    pub reason: String,
+}
+
+/// Returned by `RpcClient` when every configured endpoint failed, listing
+/// each one tried and why.
+#[derive(Debug, Clone)]
+pub struct AllEndpointsFailed {
+    pub attempts: Vec<EndpointFailure>,
+}
+
+impl std::fmt::Display for AllEndpointsFailed {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "all {} rpc endpoint(s) failed:", self.attempts.len())?;
+        for attempt in &self.attempts {
+            writeln!(f, "  - {}: {}", attempt.url, attempt.reason)?;
+        }
+        Ok(())
+    }
+}
+
+impl std::error::Error for AllEndpointsFailed {}
+
+/// Which wire dialect `RpcClient` speaks to its endpoints. `Rest` is the
+/// bespoke `address/{addr}/balance`-style dialect this client originally
+/// spoke; `JsonRpc` talks JSON-RPC 2.0 to a standard node RPC endpoint.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum RpcDialect {
+    #[default]
+    Rest,
+    JsonRpc,
+}
+
+#[derive(Debug)]
 pub struct RpcClient {
-    pub base: Url,
+    pub dialect: RpcDialect,
     http: reqwest::blocking::Client,
+    endpoints: Vec<Endpoint>,
+    next_id: AtomicU64,
+    /// Cached result of the `server/version`/`getinfo` handshake; populated
+    /// lazily on the first call to `server_info`/`supports`.
+    server_info: std::sync::Mutex<Option<ServerInfo>>,
+}
+
+impl Clone for RpcClient {
+    fn clone(&self) -> Self {
+        Self {
+            dialect: self.dialect,
+            http: self.http.clone(),
+            endpoints: self.endpoints.clone(),
+            next_id: AtomicU64::new(self.next_id.load(Ordering::Relaxed)),
+            server_info: std::sync::Mutex::new(self.server_info.lock().unwrap().clone()),
+        }
+    }
+}
+
+/// A JSON-RPC 2.0 request envelope, `id` auto-incremented by `RpcClient`.
+#[derive(Serialize)]
+struct JsonRpcRequest<'a> {
+    jsonrpc: &'static str,
+    id: u64,
+    method: &'a str,
+    params: serde_json::Value,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcResponse<T> {
+    #[serde(default)]
+    id: serde_json::Value,
+    #[serde(default)]
+    result: Option<T>,
+    #[serde(default)]
+    error: Option<JsonRpcErrorObject>,
+}
+
+#[derive(Deserialize)]
+struct JsonRpcErrorObject {
+    code: i64,
+    message: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+/// A JSON-RPC `{code, message, data}` error surfaced through `anyhow`, with
+/// the numeric code preserved so callers can match on well-known codes
+/// (e.g. `-32601` method not found) instead of parsing the message text.
+#[derive(Debug)]
+pub struct JsonRpcError {
+    pub code: i64,
+    pub message: String,
+    pub data: Option<serde_json::Value>,
+}
+
+impl std::fmt::Display for JsonRpcError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "rpc error {}: {}", self.code, self.message)
+    }
+}
+
+impl std::error::Error for JsonRpcError {}
+
+impl From<JsonRpcErrorObject> for JsonRpcError {
+    fn from(e: JsonRpcErrorObject) -> Self {
+        JsonRpcError {
+            code: e.code,
+            message: e.message,
+            data: e.data,
+        }
+    }
 }
 
 impl RpcClient {
     pub fn new(base: &str) -> anyhow::Result<Self> {
-        let client = reqwest::blocking::Client::builder()
-            .timeout(std::time::Duration::from_secs(10))
+        Self::with_endpoints(&[base], RpcDialect::Rest, DEFAULT_TIMEOUT_SECS)
+    }
+
+    /// Build a client that speaks JSON-RPC 2.0 to `base` instead of the
+    /// bespoke REST dialect.
+    pub fn new_json_rpc(base: &str) -> anyhow::Result<Self> {
+        Self::with_endpoints(&[base], RpcDialect::JsonRpc, DEFAULT_TIMEOUT_SECS)
+    }
+
+    pub fn with_dialect(base: &str, dialect: RpcDialect) -> anyhow::Result<Self> {
+        Self::with_endpoints(&[base], dialect, DEFAULT_TIMEOUT_SECS)
+    }
+
+    /// Build a client that fails over across `bases`, in order, retrying
+    /// each with exponential backoff before moving to the next.
+    pub fn with_endpoints(
+        bases: &[&str],
+        dialect: RpcDialect,
+        timeout_secs: u64,
+    ) -> anyhow::Result<Self> {
+        anyhow::ensure!(!bases.is_empty(), "RpcClient needs at least one endpoint");
+        let http = reqwest::blocking::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
             .build()?; // build here, because builder returns ClientBuilder
+        let endpoints = bases
+            .iter()
+            .map(|b| Ok(Endpoint::new(b.parse()?)))
+            .collect::<anyhow::Result<Vec<_>>>()?;
 
         Ok(Self {
-            base: base.parse()?,
-            http: client,
+            dialect,
+            http,
+            endpoints,
+            next_id: AtomicU64::new(1),
+            server_info: std::sync::Mutex::new(None),
+        })
+    }
+
+    /// Build a blocking client from the wallet's `Config`, trying `rpc_url`
+    /// first, then `rpc_urls`, then `rpc_fallback_urls`, deduplicated in
+    /// that order, honoring the configured timeout.
+    pub fn from_config(config: &crate::config::Config) -> anyhow::Result<Self> {
+        let mut seen = std::collections::HashSet::new();
+        let urls: Vec<&str> = std::iter::once(config.rpc_url.as_str())
+            .chain(config.rpc_urls.iter().map(String::as_str))
+            .chain(config.rpc_fallback_urls.iter().map(String::as_str))
+            .filter(|u| seen.insert(*u))
+            .collect();
+        Self::with_endpoints(&urls, RpcDialect::Rest, config.rpc_timeout_secs)
+    }
+
+    /// The primary endpoint, for display purposes (e.g. settings menus).
+    pub fn base(&self) -> &Url {
+        &self.endpoints[0].base
+    }
+
+    /// Endpoint indices ordered healthiest-first (ties keep configured order).
+    fn endpoint_order(&self) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.endpoints.len()).collect();
+        order.sort_by_key(|&i| std::cmp::Reverse(self.endpoints[i].health.load(Ordering::Relaxed)));
+        order
+    }
+
+    /// Run `attempt` against each endpoint in health order, retrying a
+    /// `Retryable` outcome with exponential backoff up to
+    /// `RETRY_ATTEMPTS_PER_ENDPOINT` times before failing over to the next
+    /// endpoint. Returns `AllEndpointsFailed` only once every endpoint has
+    /// been tried and exhausted.
+    fn execute<T>(&self, mut attempt: impl FnMut(&Url) -> AttemptOutcome<T>) -> anyhow::Result<T> {
+        let mut failures = Vec::with_capacity(self.endpoints.len());
+        for idx in self.endpoint_order() {
+            let endpoint = &self.endpoints[idx];
+            let mut reason = String::new();
+            for attempt_no in 0..RETRY_ATTEMPTS_PER_ENDPOINT {
+                match attempt(&endpoint.base) {
+                    AttemptOutcome::Success(value) => {
+                        endpoint.health.fetch_add(1, Ordering::Relaxed);
+                        return Ok(value);
+                    }
+                    AttemptOutcome::Fatal(why) => {
+                        reason = why;
+                        break;
+                    }
+                    AttemptOutcome::Retryable(why) => {
+                        reason = why;
+                        if attempt_no + 1 < RETRY_ATTEMPTS_PER_ENDPOINT {
+                            std::thread::sleep(backoff_delay(attempt_no));
+                        }
+                    }
+                }
+            }
+            endpoint.health.fetch_sub(1, Ordering::Relaxed);
+            failures.push(EndpointFailure {
+                url: endpoint.base.to_string(),
+                reason,
+            });
+        }
+        Err(AllEndpointsFailed { attempts: failures }.into())
+    }
+
+    /// Issue a single JSON-RPC 2.0 call, regardless of `self.dialect`, with
+    /// the same retry/failover behavior as the REST helpers.
+    pub fn call<T: DeserializeOwned>(&self, method: &str, params: serde_json::Value) -> anyhow::Result<T> {
+        self.execute(|base| {
+            let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+            let req = JsonRpcRequest {
+                jsonrpc: "2.0",
+                id,
+                method,
+                params: params.clone(),
+            };
+            match self.http.post(base.clone()).json(&req).send() {
+                Ok(resp) => {
+                    let status = resp.status();
+                    if !status.is_success() {
+                        return if status.is_server_error() {
+                            AttemptOutcome::Retryable(format!("http {status}"))
+                        } else {
+                            AttemptOutcome::Fatal(format!("http {status}"))
+                        };
+                    }
+                    match resp.json::<JsonRpcResponse<T>>() {
+                        Ok(parsed) => match parsed.error {
+                            Some(err) => AttemptOutcome::Fatal(JsonRpcError::from(err).to_string()),
+                            None => match parsed.result {
+                                Some(v) => AttemptOutcome::Success(v),
+                                None => AttemptOutcome::Fatal(format!(
+                                    "rpc response for {method} had neither result nor error"
+                                )),
+                            },
+                        },
+                        Err(e) => AttemptOutcome::Fatal(format!("decode error: {e}")),
+                    }
+                }
+                Err(e) if e.is_timeout() || e.is_connect() => AttemptOutcome::Retryable(e.to_string()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }
+        })
+    }
+
+    /// Issue many JSON-RPC 2.0 calls as a single batch request, demultiplexing
+    /// responses by id (the server may return them out of order). Used when a
+    /// UTXO scan needs to fan out many calls in one round trip. The whole
+    /// batch retries/fails-over as one unit.
+    pub fn call_batch<T: DeserializeOwned>(
+        &self,
+        calls: &[(&str, serde_json::Value)],
+    ) -> anyhow::Result<Vec<anyhow::Result<T>>> {
+        if calls.is_empty() {
+            return Ok(Vec::new());
+        }
+
+        self.execute(|base| {
+            let mut index_by_id = HashMap::with_capacity(calls.len());
+            let requests: Vec<JsonRpcRequest> = calls
+                .iter()
+                .enumerate()
+                .map(|(i, (method, params))| {
+                    let id = self.next_id.fetch_add(1, Ordering::Relaxed);
+                    index_by_id.insert(id, i);
+                    JsonRpcRequest {
+                        jsonrpc: "2.0",
+                        id,
+                        method,
+                        params: params.clone(),
+                    }
+                })
+                .collect();
+
+            let resp = match self.http.post(base.clone()).json(&requests).send() {
+                Ok(resp) => resp,
+                Err(e) if e.is_timeout() || e.is_connect() => {
+                    return AttemptOutcome::Retryable(e.to_string())
+                }
+                Err(e) => return AttemptOutcome::Fatal(e.to_string()),
+            };
+            let status = resp.status();
+            if !status.is_success() {
+                return if status.is_server_error() {
+                    AttemptOutcome::Retryable(format!("http {status}"))
+                } else {
+                    AttemptOutcome::Fatal(format!("http {status}"))
+                };
+            }
+            let responses: Vec<JsonRpcResponse<T>> = match resp.json() {
+                Ok(r) => r,
+                Err(e) => return AttemptOutcome::Fatal(format!("decode error: {e}")),
+            };
+
+            let mut out: Vec<Option<anyhow::Result<T>>> = (0..calls.len()).map(|_| None).collect();
+            for resp in responses {
+                let id = match resp.id.as_u64() {
+                    Some(id) => id,
+                    None => return AttemptOutcome::Fatal("rpc batch response had a non-numeric id".into()),
+                };
+                let i = match index_by_id.get(&id) {
+                    Some(i) => *i,
+                    None => {
+                        return AttemptOutcome::Fatal(format!(
+                            "rpc batch response id {id} matched no request"
+                        ))
+                    }
+                };
+                out[i] = Some(match resp.error {
+                    Some(err) => Err(JsonRpcError::from(err).into()),
+                    None => resp
+                        .result
+                        .ok_or_else(|| anyhow::anyhow!("rpc batch response had neither result nor error")),
+                });
+            }
+
+            match out
+                .into_iter()
+                .enumerate()
+                .map(|(i, slot)| slot.ok_or_else(|| anyhow::anyhow!("rpc batch response missing for request {i}")))
+                .collect::<anyhow::Result<Vec<_>>>()
+            {
+                Ok(v) => AttemptOutcome::Success(v),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }
         })
     }
 
     pub fn get_balance(&self, addr: &str) -> anyhow::Result<BalanceResp> {
-        let url = self.base.join(&format!("address/{}/balance", addr))?;
-        let resp = self.http.get(url).send()?;
-        if !resp.status().is_success() {
-            anyhow::bail!("balance http {}", resp.status());
+        match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match balance_url(base, addr) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }),
+            RpcDialect::JsonRpc => self.call("getbalance", serde_json::json!([addr])),
         }
-        Ok(resp.json::<BalanceResp>()?)
     }
 
     pub fn get_utxos(&self, addr: &str) -> anyhow::Result<Vec<Utxo>> {
-        let url = self.base.join(&format!("address/{}/utxos", addr))?;
-        let resp = self.http.get(url).send()?;
-        if !resp.status().is_success() {
-            anyhow::bail!("utxos http {}", resp.status());
+        match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match utxos_url(base, addr) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }),
+            RpcDialect::JsonRpc => self.call("listunspent", serde_json::json!([addr])),
         }
-        Ok(resp.json::<Vec<Utxo>>()?)
     }
 
     pub fn get_history(&self, addr: &str, limit: usize) -> anyhow::Result<Vec<TxHistoryItem>> {
-        let url = self
-            .base
-            .join(&format!("address/{}/history?limit={}", addr, limit))?;
-        let resp = self.http.get(url).send()?;
-        if !resp.status().is_success() {
-            anyhow::bail!("history http {}", resp.status());
-        }
-        Ok(resp.json::<Vec<TxHistoryItem>>()?)
+        self.execute(|base| match history_url(base, addr, limit) {
+            Ok(url) => classify_blocking(self.http.get(url).send()),
+            Err(e) => AttemptOutcome::Fatal(e.to_string()),
+        })
     }
 
     pub fn broadcast(&self, tx: &serde_json::Value) -> anyhow::Result<BroadcastResp> {
-        let url = self.base.join("tx/broadcast")?;
-        let resp = self.http.post(url).json(tx).send()?;
-        if !resp.status().is_success() {
-            anyhow::bail!("broadcast http {}", resp.status());
+        self.execute(|base| match broadcast_url(base) {
+            Ok(url) => classify_blocking(self.http.post(url).json(tx).send()),
+            Err(e) => AttemptOutcome::Fatal(e.to_string()),
+        })
+    }
+
+    /// Current chain tip height, used by `wallet::info::fetch_wallet_info` to
+    /// decide whether a coinbase output is still within the maturity window.
+    pub fn get_block_height(&self) -> anyhow::Result<u64> {
+        match self.dialect {
+            RpcDialect::Rest => {
+                let resp: BlockHeightResp = self.execute(|base| match block_height_url(base) {
+                    Ok(url) => classify_blocking(self.http.get(url).send()),
+                    Err(e) => AttemptOutcome::Fatal(e.to_string()),
+                })?;
+                Ok(resp.height)
+            }
+            RpcDialect::JsonRpc => self.call("getblockcount", serde_json::json!([])),
+        }
+    }
+
+    /// Fetch block `height` in full, for `wallet::scan`'s block-by-block
+    /// rescan. Unlike `get_utxos`/`get_history`, this doesn't assume the
+    /// node maintains an address index -- it's the fallback scan path uses
+    /// when no such index is available.
+    pub fn get_block(&self, height: u64) -> anyhow::Result<Block> {
+        match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match block_url(base, height) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }),
+            RpcDialect::JsonRpc => self.call("getblock", serde_json::json!([height])),
+        }
+    }
+
+    /// Like `get_history`, but only returns items at or after `since_height`,
+    /// so a caller with a persisted sync cursor (see `crate::sync`) doesn't
+    /// have to refetch history it already has.
+    pub fn get_history_since(
+        &self,
+        addr: &str,
+        since_height: u64,
+        limit: usize,
+    ) -> anyhow::Result<Vec<TxHistoryItem>> {
+        match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match history_since_url(base, addr, since_height, limit) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }),
+            RpcDialect::JsonRpc => self.call(
+                "listsinceheight",
+                serde_json::json!([addr, since_height, limit]),
+            ),
+        }
+    }
+
+    /// Fetch fee-rate statistics over the last `blocks` blocks. Tries the
+    /// node's native fee-history endpoint first (skipped entirely if the
+    /// handshake says the node doesn't advertise it); falls back to pulling
+    /// recently confirmed transactions and computing percentiles
+    /// client-side (see `FeeHistory::from_samples`).
+    pub fn get_fee_history(&self, blocks: usize) -> anyhow::Result<FeeHistory> {
+        if !self.supports("fee_history") {
+            return self.estimate_fee_history(blocks);
+        }
+        let native = match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match fee_history_url(base, blocks) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            }),
+            RpcDialect::JsonRpc => self.call("getfeehistory", serde_json::json!([blocks])),
+        };
+        match native {
+            Ok(hist) => Ok(hist),
+            Err(_) => self.estimate_fee_history(blocks),
+        }
+    }
+
+    /// Run (and cache) the `server/version`/`getinfo` handshake, rejecting
+    /// the node if it reports a protocol version below
+    /// `MIN_SUPPORTED_PROTOCOL`.
+    pub fn server_info(&self) -> anyhow::Result<ServerInfo> {
+        if let Some(info) = self.server_info.lock().unwrap().clone() {
+            return Ok(info);
+        }
+        let info: ServerInfo = match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match server_version_url(base) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            })?,
+            RpcDialect::JsonRpc => self.call("getinfo", serde_json::json!([]))?,
+        };
+        anyhow::ensure!(
+            info.protocol_version >= MIN_SUPPORTED_PROTOCOL,
+            "node protocol version {} is below the minimum supported version {MIN_SUPPORTED_PROTOCOL}",
+            info.protocol_version,
+        );
+        *self.server_info.lock().unwrap() = Some(info.clone());
+        Ok(info)
+    }
+
+    /// Whether the connected node advertises `capability` (e.g.
+    /// `"fee_history"`, `"json_rpc"`). Treats a failed/unreachable
+    /// handshake as "unsupported" rather than propagating the error, since
+    /// callers use this to decide whether to *try* something, not to learn
+    /// why a handshake failed — use `server_info` directly for that.
+    pub fn supports(&self, capability: &str) -> bool {
+        self.server_info()
+            .map(|info| info.capabilities.iter().any(|c| c == capability))
+            .unwrap_or(false)
+    }
+
+    fn estimate_fee_history(&self, blocks: usize) -> anyhow::Result<FeeHistory> {
+        let samples: Vec<TxHistoryItem> = match self.dialect {
+            RpcDialect::Rest => self.execute(|base| match recent_fees_url(base, blocks) {
+                Ok(url) => classify_blocking(self.http.get(url).send()),
+                Err(e) => AttemptOutcome::Fatal(e.to_string()),
+            })?,
+            RpcDialect::JsonRpc => self.call("listrecentfees", serde_json::json!([blocks]))?,
+        };
+        Ok(FeeHistory::from_samples(blocks, &samples))
+    }
+
+    /// Convenience wrapper around `get_fee_history` that maps a priority
+    /// straight to a fee rate in sat/byte.
+    pub fn estimate_fee(&self, priority: FeePriority, blocks: usize) -> anyhow::Result<u64> {
+        Ok(self.get_fee_history(blocks)?.percentile_for(priority))
+    }
+}
+
+/// Which percentile `RpcClient::estimate_fee` should pick from `FeeHistory`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum FeePriority {
+    Low,
+    Medium,
+    High,
+}
+
+/// Fee-rate percentile buckets observed over the requested block window.
+/// Every rate here is **sat/byte** (a per-byte rate), not a total fee —
+/// multiply by a transaction's estimated size to get the absolute fee to pay.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FeeHistory {
+    pub blocks_sampled: usize,
+    /// The lowest observed fee rate; roughly what a node's mempool minimum
+    /// relay fee would be.
+    pub base_fee_sat_per_byte: u64,
+    pub p25_sat_per_byte: u64,
+    pub p50_sat_per_byte: u64,
+    pub p75_sat_per_byte: u64,
+}
+
+impl FeeHistory {
+    pub fn percentile_for(&self, priority: FeePriority) -> u64 {
+        match priority {
+            FeePriority::Low => self.p25_sat_per_byte,
+            FeePriority::Medium => self.p50_sat_per_byte,
+            FeePriority::High => self.p75_sat_per_byte,
+        }
+    }
+
+    /// Client-side fallback for nodes with no fee-history endpoint: turns a
+    /// batch of confirmed `TxHistoryItem`s into fee-rate percentiles by
+    /// approximating each one's rate as `fee / ASSUMED_TX_SIZE_BYTES`.
+    pub fn from_samples(blocks_sampled: usize, samples: &[TxHistoryItem]) -> Self {
+        let mut rates: Vec<u64> = samples
+            .iter()
+            .filter(|item| item.height.is_some())
+            .filter_map(|item| item.fee)
+            .map(|fee| fee / ASSUMED_TX_SIZE_BYTES)
+            .collect();
+        rates.sort_unstable();
+
+        FeeHistory {
+            blocks_sampled,
+            base_fee_sat_per_byte: rates.first().copied().unwrap_or(1).max(1),
+            p25_sat_per_byte: percentile(&rates, 25),
+            p50_sat_per_byte: percentile(&rates, 50),
+            p75_sat_per_byte: percentile(&rates, 75),
         }
-        Ok(resp.json::<BroadcastResp>()?)
     }
 }
 
+fn percentile(sorted_rates: &[u64], pct: usize) -> u64 {
+    if sorted_rates.is_empty() {
+        return 1;
+    }
+    let idx = (sorted_rates.len() - 1) * pct / 100;
+    sorted_rates[idx].max(1)
+}
+
+/// An async counterpart to `RpcClient`, built on `reqwest::Client` instead of
+/// `reqwest::blocking::Client`, so a caller can `tokio::join!` many address
+/// lookups (balance/UTXOs/history) concurrently instead of serializing them.
+/// Speaks only the REST dialect against a single endpoint for now; reach for
+/// the blocking `RpcClient` for multi-endpoint failover or JSON-RPC.
+#[derive(Debug, Clone)]
+pub struct AsyncRpcClient {
+    pub base: Url,
+    http: reqwest::Client,
+}
+
+impl AsyncRpcClient {
+    pub fn new(base: &str) -> anyhow::Result<Self> {
+        Self::with_timeout(base, DEFAULT_TIMEOUT_SECS)
+    }
+
+    pub fn with_timeout(base: &str, timeout_secs: u64) -> anyhow::Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(timeout_secs))
+            .build()?;
+        Ok(Self {
+            base: base.parse()?,
+            http: client,
+        })
+    }
+
+    /// Build an async client from the wallet's `Config`, honoring its
+    /// primary RPC URL and timeout.
+    pub fn from_config(config: &crate::config::Config) -> anyhow::Result<Self> {
+        Self::with_timeout(&config.rpc_url, config.rpc_timeout_secs)
+    }
+
+    pub async fn get_balance(&self, addr: &str) -> anyhow::Result<BalanceResp> {
+        let resp = self.http.get(balance_url(&self.base, addr)?).send().await?;
+        ensure_success("balance", resp.status())?;
+        Ok(resp.json::<BalanceResp>().await?)
+    }
+
+    pub async fn get_utxos(&self, addr: &str) -> anyhow::Result<Vec<Utxo>> {
+        let resp = self.http.get(utxos_url(&self.base, addr)?).send().await?;
+        ensure_success("utxos", resp.status())?;
+        Ok(resp.json::<Vec<Utxo>>().await?)
+    }
+
+    pub async fn get_history(&self, addr: &str, limit: usize) -> anyhow::Result<Vec<TxHistoryItem>> {
+        let resp = self
+            .http
+            .get(history_url(&self.base, addr, limit)?)
+            .send()
+            .await?;
+        ensure_success("history", resp.status())?;
+        Ok(resp.json::<Vec<TxHistoryItem>>().await?)
+    }
+
+    pub async fn broadcast(&self, tx: &serde_json::Value) -> anyhow::Result<BroadcastResp> {
+        let resp = self
+            .http
+            .post(broadcast_url(&self.base)?)
+            .json(tx)
+            .send()
+            .await?;
+        ensure_success("broadcast", resp.status())?;
+        Ok(resp.json::<BroadcastResp>().await?)
+    }
+}
+
+/// The result of the `server/version`/`getinfo` handshake: the node's
+/// protocol version and the capability/method names it advertises, so a
+/// caller can branch on `RpcClient::supports` instead of discovering gaps
+/// via failed requests.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ServerInfo {
+    pub protocol_version: u32,
+    #[serde(default)]
+    pub capabilities: Vec<String>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceResp {
     pub confirmed: u64,
     pub pending: u64,
 }
 
+/// Response shape for `block_height_url`; the REST dialect wraps the tip
+/// height in an object rather than returning a bare number.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BlockHeightResp {
+    height: u64,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct Utxo {
     pub txid: String,
@@ -71,6 +810,36 @@ pub struct Utxo {
     pub value: u64,
 }
 
+/// An output created in a scanned block, as seen by `RpcClient::get_block`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockOutput {
+    pub txid: String,
+    pub vout: u32,
+    pub address: String,
+    pub value: u64,
+    #[serde(default)]
+    pub is_coinbase: bool,
+}
+
+/// An input spent in a scanned block, identifying the output it consumes.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BlockInput {
+    pub prevout_txid: String,
+    pub prevout_vout: u32,
+}
+
+/// A full block, as needed by `wallet::scan` to match outputs/spends
+/// against a wallet's addresses without relying on the node's address index.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Block {
+    pub height: u64,
+    pub hash: String,
+    #[serde(default)]
+    pub outputs: Vec<BlockOutput>,
+    #[serde(default)]
+    pub spent: Vec<BlockInput>,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct BroadcastResp {
     pub txid: String,
@@ -83,4 +852,9 @@ pub struct TxHistoryItem {
     pub timestamp: Option<u64>,
     pub delta: i64, // positive for received, negative for sent (incl. fee)
     pub fee: Option<u64>,
+    /// Whether this item is a coinbase (block reward) output, so callers
+    /// like `wallet::info::fetch_wallet_info` can hold it in the immature
+    /// bucket until it clears `wallet::info::COINBASE_MATURITY` confirmations.
+    #[serde(default)]
+    pub is_coinbase: bool,
 }
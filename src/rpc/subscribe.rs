@@ -0,0 +1,340 @@
+//! WebSocket push subscriptions, layered over the same JSON-RPC 2.0 dialect
+//! `RpcClient` speaks over HTTP: `subscribe_new_blocks`/`subscribe_address`
+//! open one shared WebSocket, match notification frames to subscribers by
+//! subscription id, and transparently reconnect (re-issuing every live
+//! subscription) if the connection drops. Lets a GUI/TUI update balances in
+//! real time instead of polling `RpcClient::get_balance` on a timer.
+
+use futures_util::{SinkExt, StreamExt};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::{mpsc, oneshot, Mutex};
+use tokio_tungstenite::tungstenite::Message;
+use url::Url;
+
+use crate::rpc::JsonRpcError;
+
+const RECONNECT_BASE_DELAY: Duration = Duration::from_millis(200);
+const RECONNECT_MAX_DELAY: Duration = Duration::from_secs(10);
+
+/// A new block was connected to the chain tip.
+#[derive(Debug, Clone, Deserialize)]
+pub struct BlockEvent {
+    pub height: u64,
+    pub hash: String,
+}
+
+/// A UTXO affecting a subscribed address appeared or was spent.
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum AddressEvent {
+    NewUtxo { txid: String, vout: u32, value: u64 },
+    SpentUtxo { txid: String, vout: u32 },
+}
+
+/// What a subscriber asked for, kept around so a reconnect can re-issue the
+/// same `subscribe_*` call and pick up a (possibly new) subscription id.
+#[derive(Clone)]
+enum SubscribeRequest {
+    NewBlocks,
+    Address(String),
+}
+
+impl SubscribeRequest {
+    fn method(&self) -> &'static str {
+        match self {
+            SubscribeRequest::NewBlocks => "subscribe_new_blocks",
+            SubscribeRequest::Address(_) => "subscribe_address",
+        }
+    }
+
+    fn params(&self) -> serde_json::Value {
+        match self {
+            SubscribeRequest::NewBlocks => serde_json::json!([]),
+            SubscribeRequest::Address(addr) => serde_json::json!([addr]),
+        }
+    }
+}
+
+/// Where a subscription's decoded events get forwarded.
+enum Sink {
+    NewBlocks(mpsc::UnboundedSender<BlockEvent>),
+    Address(mpsc::UnboundedSender<AddressEvent>),
+}
+
+struct Subscription {
+    request: SubscribeRequest,
+    sink: Sink,
+}
+
+#[derive(Deserialize)]
+struct ErrorObject {
+    code: i64,
+    message: String,
+    #[serde(default)]
+    data: Option<serde_json::Value>,
+}
+
+impl From<ErrorObject> for JsonRpcError {
+    fn from(e: ErrorObject) -> Self {
+        JsonRpcError {
+            code: e.code,
+            message: e.message,
+            data: e.data,
+        }
+    }
+}
+
+struct Inner {
+    ws_url: Url,
+    next_local_id: AtomicU64,
+    next_request_id: AtomicU64,
+    /// Keyed by a locally-assigned id that's stable across reconnects.
+    subscriptions: Mutex<HashMap<u64, Subscription>>,
+    /// Server-assigned subscription id -> local id; rebuilt on every reconnect.
+    server_to_local: Mutex<HashMap<String, u64>>,
+    /// Request id -> reply channel for an in-flight `subscribe_*` call.
+    pending_calls: Mutex<HashMap<u64, oneshot::Sender<Result<serde_json::Value, JsonRpcError>>>>,
+    /// Set while a WebSocket connection is live; `None` between reconnects.
+    outbound: Mutex<Option<mpsc::UnboundedSender<Message>>>,
+}
+
+/// A handle to a live (auto-reconnecting) WebSocket subscription feed.
+/// Cloning shares the same underlying connection and subscriptions.
+#[derive(Clone)]
+pub struct SubscriptionClient {
+    inner: Arc<Inner>,
+}
+
+impl SubscriptionClient {
+    /// Open `ws_url` and start the background reconnect loop.
+    pub async fn connect(ws_url: &str) -> anyhow::Result<Self> {
+        let inner = Arc::new(Inner {
+            ws_url: ws_url.parse()?,
+            next_local_id: AtomicU64::new(1),
+            next_request_id: AtomicU64::new(1),
+            subscriptions: Mutex::new(HashMap::new()),
+            server_to_local: Mutex::new(HashMap::new()),
+            pending_calls: Mutex::new(HashMap::new()),
+            outbound: Mutex::new(None),
+        });
+        tokio::spawn(run_connection_loop(inner.clone()));
+        Ok(SubscriptionClient { inner })
+    }
+
+    /// Stream of new block connects.
+    pub async fn subscribe_new_blocks(&self) -> anyhow::Result<mpsc::UnboundedReceiver<BlockEvent>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribe(SubscribeRequest::NewBlocks, Sink::NewBlocks(tx)).await;
+        Ok(rx)
+    }
+
+    /// Stream of new/spent UTXOs touching `addr`.
+    pub async fn subscribe_address(
+        &self,
+        addr: &str,
+    ) -> anyhow::Result<mpsc::UnboundedReceiver<AddressEvent>> {
+        let (tx, rx) = mpsc::unbounded_channel();
+        self.subscribe(SubscribeRequest::Address(addr.to_string()), Sink::Address(tx))
+            .await;
+        Ok(rx)
+    }
+
+    /// Register the subscription locally, then issue it immediately if
+    /// already connected. If not yet connected (or the connection drops
+    /// before the reply arrives), `run_connection_loop`'s `resubscribe_all`
+    /// re-issues it on every (re)connect, so this never has to be retried
+    /// by the caller.
+    async fn subscribe(&self, request: SubscribeRequest, sink: Sink) {
+        let local_id = self.inner.next_local_id.fetch_add(1, Ordering::Relaxed);
+        self.inner
+            .subscriptions
+            .lock()
+            .await
+            .insert(local_id, Subscription { request: request.clone(), sink });
+
+        if self.inner.outbound.lock().await.is_some() {
+            if let Ok(result) = send_call(&self.inner, request.method(), request.params()).await {
+                if let Some(server_id) = result.as_str() {
+                    self.inner
+                        .server_to_local
+                        .lock()
+                        .await
+                        .insert(server_id.to_string(), local_id);
+                }
+            }
+        }
+    }
+}
+
+async fn run_connection_loop(inner: Arc<Inner>) {
+    let mut attempt: u32 = 0;
+    loop {
+        match connect_once(inner.clone()).await {
+            Ok(()) => attempt = 0, // clean disconnect; reset backoff to the base delay
+            Err(_) => attempt = attempt.saturating_add(1),
+        }
+        *inner.outbound.lock().await = None;
+        inner.pending_calls.lock().await.clear();
+        tokio::time::sleep(reconnect_delay(attempt)).await;
+    }
+}
+
+fn reconnect_delay(attempt: u32) -> Duration {
+    RECONNECT_BASE_DELAY
+        .saturating_mul(1u32 << attempt.min(16))
+        .min(RECONNECT_MAX_DELAY)
+}
+
+async fn connect_once(inner: Arc<Inner>) -> anyhow::Result<()> {
+    let (ws_stream, _) = tokio_tungstenite::connect_async(inner.ws_url.as_str()).await?;
+    let (mut write, mut read) = ws_stream.split();
+    let (outbound_tx, mut outbound_rx) = mpsc::unbounded_channel::<Message>();
+    *inner.outbound.lock().await = Some(outbound_tx);
+
+    let write_task = tokio::spawn(async move {
+        while let Some(msg) = outbound_rx.recv().await {
+            if write.send(msg).await.is_err() {
+                break;
+            }
+        }
+    });
+
+    let read_inner = inner.clone();
+    let read_task = tokio::spawn(async move {
+        while let Some(frame) = read.next().await {
+            match frame {
+                Ok(Message::Text(text)) => handle_frame(&read_inner, &text).await,
+                Ok(Message::Close(_)) => break,
+                Ok(_) => {}
+                Err(_) => break,
+            }
+        }
+        // Fail every call still awaiting a reply on this connection.
+        *read_inner.outbound.lock().await = None;
+        read_inner.pending_calls.lock().await.clear();
+    });
+
+    resubscribe_all(&inner).await;
+
+    // Either task finishing means the connection is down; tear down and let
+    // `run_connection_loop` reconnect.
+    tokio::select! {
+        _ = write_task => {},
+        _ = read_task => {},
+    }
+    Ok(())
+}
+
+/// Re-issue every locally-tracked subscription against a fresh connection,
+/// since a new connection has no memory of the old server-assigned ids.
+async fn resubscribe_all(inner: &Arc<Inner>) {
+    let requests: Vec<(u64, SubscribeRequest)> = inner
+        .subscriptions
+        .lock()
+        .await
+        .iter()
+        .map(|(id, sub)| (*id, sub.request.clone()))
+        .collect();
+    inner.server_to_local.lock().await.clear();
+
+    for (local_id, request) in requests {
+        if let Ok(result) = send_call(inner, request.method(), request.params()).await {
+            if let Some(server_id) = result.as_str() {
+                inner
+                    .server_to_local
+                    .lock()
+                    .await
+                    .insert(server_id.to_string(), local_id);
+            }
+        }
+    }
+}
+
+/// Send one JSON-RPC 2.0 call over the current connection and await its reply.
+async fn send_call(
+    inner: &Inner,
+    method: &str,
+    params: serde_json::Value,
+) -> anyhow::Result<serde_json::Value> {
+    let id = inner.next_request_id.fetch_add(1, Ordering::Relaxed);
+    let (tx, rx) = oneshot::channel();
+    inner.pending_calls.lock().await.insert(id, tx);
+
+    let outbound = inner
+        .outbound
+        .lock()
+        .await
+        .clone()
+        .ok_or_else(|| anyhow::anyhow!("subscription client is not connected"))?;
+    let req = serde_json::json!({"jsonrpc": "2.0", "id": id, "method": method, "params": params});
+    outbound.send(Message::Text(serde_json::to_string(&req)?))?;
+
+    match rx.await {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(e)) => Err(e.into()),
+        Err(_) => Err(anyhow::anyhow!(
+            "subscription client disconnected before {method} got a reply"
+        )),
+    }
+}
+
+async fn handle_frame(inner: &Inner, text: &str) {
+    let Ok(value) = serde_json::from_str::<serde_json::Value>(text) else {
+        return;
+    };
+
+    if let Some(id) = value.get("id").and_then(|v| v.as_u64()) {
+        if let Some(tx) = inner.pending_calls.lock().await.remove(&id) {
+            let reply = match value.get("error").cloned() {
+                Some(err) => match serde_json::from_value::<ErrorObject>(err) {
+                    Ok(err) => Err(err.into()),
+                    Err(_) => Err(JsonRpcError {
+                        code: 0,
+                        message: "malformed rpc error object".to_string(),
+                        data: None,
+                    }),
+                },
+                None => Ok(value.get("result").cloned().unwrap_or(serde_json::Value::Null)),
+            };
+            let _ = tx.send(reply);
+        }
+        return;
+    }
+
+    if value.get("method").and_then(|m| m.as_str()) == Some("subscription") {
+        if let Some(params) = value.get("params") {
+            if let (Some(server_id), Some(result)) = (
+                params.get("subscription").and_then(|v| v.as_str()),
+                params.get("result"),
+            ) {
+                dispatch_event(inner, server_id, result).await;
+            }
+        }
+    }
+}
+
+async fn dispatch_event(inner: &Inner, server_id: &str, payload: &serde_json::Value) {
+    let Some(local_id) = inner.server_to_local.lock().await.get(server_id).copied() else {
+        return;
+    };
+    let subs = inner.subscriptions.lock().await;
+    let Some(sub) = subs.get(&local_id) else {
+        return;
+    };
+    match &sub.sink {
+        Sink::NewBlocks(tx) => {
+            if let Ok(event) = serde_json::from_value::<BlockEvent>(payload.clone()) {
+                let _ = tx.send(event);
+            }
+        }
+        Sink::Address(tx) => {
+            if let Ok(event) = serde_json::from_value::<AddressEvent>(payload.clone()) {
+                let _ = tx.send(event);
+            }
+        }
+    }
+}
@@ -0,0 +1,12 @@
+//! Background wallet sync: a worker thread that periodically pulls
+//! UTXOs/history for a set of addresses via `rpc::RpcClient`, persists them
+//! to a local SQLite cache, and broadcasts a `SyncEvent` after each
+//! address's sync (not only when something changed), so the wallet survives
+//! restarts and answers balance queries instantly from the cache instead of
+//! blocking on an RPC round trip every time.
+
+pub mod store;
+pub mod worker;
+
+pub use store::SyncStore;
+pub use worker::{SyncCommand, SyncEvent, WalletSync};
@@ -0,0 +1,180 @@
+//! SQLite-backed cache of UTXOs, history items, and per-address sync
+//! cursors, so `WalletSync` survives restarts without refetching everything.
+
+use crate::rpc::{BalanceResp, TxHistoryItem, Utxo};
+use rusqlite::{params, Connection};
+use std::path::Path;
+
+pub struct SyncStore {
+    conn: Connection,
+}
+
+impl SyncStore {
+    /// Open (creating if needed) the SQLite database at `path` and ensure
+    /// its schema is up to date.
+    pub fn open(path: &Path) -> anyhow::Result<Self> {
+        let conn = Connection::open(path)?;
+        let store = SyncStore { conn };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    /// An in-memory store, handy for tests.
+    pub fn open_in_memory() -> anyhow::Result<Self> {
+        let conn = Connection::open_in_memory()?;
+        let store = SyncStore { conn };
+        store.migrate()?;
+        Ok(store)
+    }
+
+    fn migrate(&self) -> anyhow::Result<()> {
+        self.conn.execute_batch(
+            "
+            CREATE TABLE IF NOT EXISTS utxos (
+                address TEXT NOT NULL,
+                txid    TEXT NOT NULL,
+                vout    INTEGER NOT NULL,
+                value   INTEGER NOT NULL,
+                PRIMARY KEY (txid, vout)
+            );
+            CREATE INDEX IF NOT EXISTS utxos_by_address ON utxos(address);
+
+            CREATE TABLE IF NOT EXISTS history (
+                address     TEXT NOT NULL,
+                txid        TEXT NOT NULL,
+                height      INTEGER,
+                timestamp   INTEGER,
+                delta       INTEGER NOT NULL,
+                fee         INTEGER,
+                is_coinbase INTEGER NOT NULL DEFAULT 0,
+                PRIMARY KEY (address, txid)
+            );
+            CREATE INDEX IF NOT EXISTS history_by_address ON history(address);
+
+            CREATE TABLE IF NOT EXISTS sync_cursor (
+                address     TEXT PRIMARY KEY,
+                last_height INTEGER NOT NULL
+            );
+            ",
+        )?;
+        Ok(())
+    }
+
+    /// Replace every cached UTXO for `address` with `utxos`.
+    pub fn replace_utxos(&mut self, address: &str, utxos: &[Utxo]) -> anyhow::Result<()> {
+        let tx = self.conn.transaction()?;
+        tx.execute("DELETE FROM utxos WHERE address = ?1", params![address])?;
+        for u in utxos {
+            tx.execute(
+                "INSERT INTO utxos (address, txid, vout, value) VALUES (?1, ?2, ?3, ?4)",
+                params![address, u.txid, u.vout, u.value as i64],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn utxos_for(&self, address: &str) -> anyhow::Result<Vec<Utxo>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT txid, vout, value FROM utxos WHERE address = ?1")?;
+        let rows = stmt.query_map(params![address], |row| {
+            Ok(Utxo {
+                txid: row.get(0)?,
+                vout: row.get::<_, i64>(1)? as u32,
+                value: row.get::<_, i64>(2)? as u64,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    /// Upsert `items` into the history cache for `address` (new items are
+    /// inserted, previously-cached ones with the same txid are updated —
+    /// e.g. when a pending tx confirms and gets a `height`).
+    pub fn upsert_history(&mut self, address: &str, items: &[TxHistoryItem]) -> anyhow::Result<()> {
+        let tx = self.conn.transaction()?;
+        for item in items {
+            tx.execute(
+                "INSERT INTO history (address, txid, height, timestamp, delta, fee, is_coinbase)
+                 VALUES (?1, ?2, ?3, ?4, ?5, ?6, ?7)
+                 ON CONFLICT(address, txid) DO UPDATE SET
+                    height = excluded.height,
+                    timestamp = excluded.timestamp,
+                    delta = excluded.delta,
+                    fee = excluded.fee,
+                    is_coinbase = excluded.is_coinbase",
+                params![
+                    address,
+                    item.txid,
+                    item.height.map(|h| h as i64),
+                    item.timestamp.map(|t| t as i64),
+                    item.delta,
+                    item.fee.map(|f| f as i64),
+                    item.is_coinbase,
+                ],
+            )?;
+        }
+        tx.commit()?;
+        Ok(())
+    }
+
+    pub fn history_for(&self, address: &str) -> anyhow::Result<Vec<TxHistoryItem>> {
+        let mut stmt = self
+            .conn
+            .prepare("SELECT txid, height, timestamp, delta, fee, is_coinbase FROM history WHERE address = ?1")?;
+        let rows = stmt.query_map(params![address], |row| {
+            Ok(TxHistoryItem {
+                txid: row.get(0)?,
+                height: row.get::<_, Option<i64>>(1)?.map(|h| h as u64),
+                timestamp: row.get::<_, Option<i64>>(2)?.map(|t| t as u64),
+                delta: row.get(3)?,
+                fee: row.get::<_, Option<i64>>(4)?.map(|f| f as u64),
+                is_coinbase: row.get(5)?,
+            })
+        })?;
+        rows.collect::<Result<Vec<_>, _>>().map_err(Into::into)
+    }
+
+    pub fn set_cursor(&self, address: &str, last_height: u64) -> anyhow::Result<()> {
+        self.conn.execute(
+            "INSERT INTO sync_cursor (address, last_height) VALUES (?1, ?2)
+             ON CONFLICT(address) DO UPDATE SET last_height = excluded.last_height",
+            params![address, last_height as i64],
+        )?;
+        Ok(())
+    }
+
+    pub fn cursor(&self, address: &str) -> anyhow::Result<Option<u64>> {
+        self.conn
+            .query_row(
+                "SELECT last_height FROM sync_cursor WHERE address = ?1",
+                params![address],
+                |row| row.get::<_, i64>(0),
+            )
+            .map(|h| Some(h as u64))
+            .or_else(|e| match e {
+                rusqlite::Error::QueryReturnedNoRows => Ok(None),
+                e => Err(e.into()),
+            })
+    }
+
+    /// Reconcile confirmed vs pending balance from the cached history: a
+    /// `TxHistoryItem` with `height == None` hasn't confirmed yet, so its
+    /// `delta` counts toward `pending` rather than `confirmed`.
+    pub fn balance(&self, address: &str) -> anyhow::Result<BalanceResp> {
+        let items = self.history_for(address)?;
+        let mut confirmed: i64 = 0;
+        let mut pending: i64 = 0;
+        for item in &items {
+            if item.height.is_some() {
+                confirmed += item.delta;
+            } else {
+                pending += item.delta;
+            }
+        }
+        Ok(BalanceResp {
+            confirmed: confirmed.max(0) as u64,
+            pending: pending.max(0) as u64,
+        })
+    }
+}
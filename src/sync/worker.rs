@@ -0,0 +1,180 @@
+//! The background worker driving `sync::SyncStore`: a dedicated thread (the
+//! RPC calls it makes are blocking) that wakes up on a timer or a command,
+//! pulls fresh UTXOs/history for every tracked address, and persists the
+//! result.
+
+use crate::rpc::RpcClient;
+use crate::sync::SyncStore;
+use std::path::Path;
+use std::sync::mpsc as std_mpsc;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+/// History fetches unbounded by a previous cursor still need a cap.
+const FULL_HISTORY_LIMIT: usize = 10_000;
+/// Capacity of the `SyncEvent` broadcast buffer. A subscriber that falls
+/// further behind stays subscribed but misses the oldest events; its next
+/// `recv` returns `RecvError::Lagged`.
+const EVENT_CHANNEL_CAPACITY: usize = 256;
+
+/// A command sent to the background sync worker.
+pub enum SyncCommand {
+    /// Sync every tracked address immediately, instead of waiting for the
+    /// next timer tick.
+    Sync,
+    /// Stop the periodic timer; `Sync` can still be sent to force a one-off
+    /// refresh while paused.
+    Pause,
+    Shutdown,
+}
+
+/// An event broadcast to every `WalletSync::subscribe` caller: `Synced` after
+/// every sync of an address, whether or not its UTXOs/history changed, and
+/// `Error` when syncing it fails. Nothing is diffed against the store.
+#[derive(Debug, Clone)]
+pub enum SyncEvent {
+    Synced { address: String, tip_height: Option<u64> },
+    Error { address: String, message: String },
+}
+
+/// A handle to a running background sync worker and its SQLite cache.
+/// Dropping it shuts the worker down and joins its thread.
+pub struct WalletSync {
+    store: Arc<Mutex<SyncStore>>,
+    commands: std_mpsc::Sender<SyncCommand>,
+    events: broadcast::Sender<SyncEvent>,
+    handle: Option<std::thread::JoinHandle<()>>,
+}
+
+impl WalletSync {
+    /// Open `store_path` and start polling `addresses` over `rpc` every
+    /// `poll_interval`.
+    pub fn spawn(
+        store_path: &Path,
+        rpc: RpcClient,
+        addresses: Vec<String>,
+        poll_interval: Duration,
+    ) -> anyhow::Result<Self> {
+        let store = Arc::new(Mutex::new(SyncStore::open(store_path)?));
+        let (cmd_tx, cmd_rx) = std_mpsc::channel();
+        let (event_tx, _) = broadcast::channel(EVENT_CHANNEL_CAPACITY);
+
+        let worker_store = store.clone();
+        let worker_events = event_tx.clone();
+        let handle = std::thread::spawn(move || {
+            run(rpc, addresses, poll_interval, worker_store, cmd_rx, worker_events);
+        });
+
+        Ok(WalletSync {
+            store,
+            commands: cmd_tx,
+            events: event_tx,
+            handle: Some(handle),
+        })
+    }
+
+    /// Read `addr`'s balance from the cache; never touches the network.
+    pub fn balance(&self, addr: &str) -> anyhow::Result<crate::rpc::BalanceResp> {
+        self.store
+            .lock()
+            .map_err(|_| anyhow::anyhow!("sync store lock poisoned"))?
+            .balance(addr)
+    }
+
+    pub fn sync_now(&self) -> anyhow::Result<()> {
+        self.commands
+            .send(SyncCommand::Sync)
+            .map_err(|_| anyhow::anyhow!("sync worker has shut down"))
+    }
+
+    pub fn pause(&self) -> anyhow::Result<()> {
+        self.commands
+            .send(SyncCommand::Pause)
+            .map_err(|_| anyhow::anyhow!("sync worker has shut down"))
+    }
+
+    /// Subscribe to synced/error events. Each subscriber gets every event
+    /// sent after it subscribes.
+    pub fn subscribe(&self) -> broadcast::Receiver<SyncEvent> {
+        self.events.subscribe()
+    }
+}
+
+impl Drop for WalletSync {
+    fn drop(&mut self) {
+        let _ = self.commands.send(SyncCommand::Shutdown);
+        if let Some(handle) = self.handle.take() {
+            let _ = handle.join();
+        }
+    }
+}
+
+fn run(
+    rpc: RpcClient,
+    addresses: Vec<String>,
+    poll_interval: Duration,
+    store: Arc<Mutex<SyncStore>>,
+    commands: std_mpsc::Receiver<SyncCommand>,
+    events: broadcast::Sender<SyncEvent>,
+) {
+    let mut paused = false;
+    loop {
+        match commands.recv_timeout(poll_interval) {
+            Ok(SyncCommand::Sync) => sync_all(&rpc, &addresses, &store, &events),
+            Ok(SyncCommand::Pause) => paused = true,
+            Ok(SyncCommand::Shutdown) => return,
+            Err(std_mpsc::RecvTimeoutError::Timeout) => {
+                if !paused {
+                    sync_all(&rpc, &addresses, &store, &events);
+                }
+            }
+            Err(std_mpsc::RecvTimeoutError::Disconnected) => return,
+        }
+    }
+}
+
+fn sync_all(
+    rpc: &RpcClient,
+    addresses: &[String],
+    store: &Arc<Mutex<SyncStore>>,
+    events: &broadcast::Sender<SyncEvent>,
+) {
+    for address in addresses {
+        let outcome = sync_one(rpc, address, store);
+        let event = match outcome {
+            Ok(tip_height) => SyncEvent::Synced {
+                address: address.clone(),
+                tip_height,
+            },
+            Err(e) => SyncEvent::Error {
+                address: address.clone(),
+                message: e.to_string(),
+            },
+        };
+        // No subscribers is a normal, common state; ignore the send error.
+        let _ = events.send(event);
+    }
+}
+
+fn sync_one(rpc: &RpcClient, address: &str, store: &Arc<Mutex<SyncStore>>) -> anyhow::Result<Option<u64>> {
+    let since_height = store
+        .lock()
+        .map_err(|_| anyhow::anyhow!("sync store lock poisoned"))?
+        .cursor(address)?;
+
+    let utxos = rpc.get_utxos(address)?;
+    let history = match since_height {
+        Some(h) => rpc.get_history_since(address, h, FULL_HISTORY_LIMIT)?,
+        None => rpc.get_history(address, FULL_HISTORY_LIMIT)?,
+    };
+    let tip_height = history.iter().filter_map(|item| item.height).max().or(since_height);
+
+    let mut store = store.lock().map_err(|_| anyhow::anyhow!("sync store lock poisoned"))?;
+    store.replace_utxos(address, &utxos)?;
+    store.upsert_history(address, &history)?;
+    if let Some(height) = tip_height {
+        store.set_cursor(address, height)?;
+    }
+    Ok(tip_height)
+}
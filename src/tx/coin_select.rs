@@ -0,0 +1,226 @@
+//! UTXO coin selection: branch-and-bound searches for a changeless (exact)
+//! match first, falling back to largest-first when no such combination
+//! exists among the available UTXOs. Mirrors Bitcoin Core's selection
+//! strategy, priced against this wallet's fee-rate-times-size fee model.
+
+use crate::rpc::Utxo;
+use serde::Serialize;
+
+/// Rough per-tx-part byte costs, consistent with `rpc::ASSUMED_TX_SIZE_BYTES`'s
+/// documented approximation: this wallet doesn't yet serialize exact
+/// Dilithium5-sized transactions for fee-estimation purposes.
+const ESTIMATED_BASE_BYTES: u64 = 50;
+const ESTIMATED_BYTES_PER_INPUT: u64 = 150;
+const ESTIMATED_BYTES_PER_OUTPUT: u64 = 40;
+
+/// At or below this, a change output would cost more to create and later spend
+/// than it's worth; fold it into the fee instead of creating it.
+const DUST_THRESHOLD: u64 = 1_000;
+
+/// Bound on the branch-and-bound search tree so a large UTXO set can't make
+/// coin selection pathologically slow.
+const MAX_SEARCH_NODES: usize = 100_000;
+
+/// How a candidate selection's fee is priced.
+#[derive(Debug, Clone, Copy)]
+pub enum FeeModel {
+    /// `--fee` was given explicitly: pay exactly this, independent of size.
+    Fixed(u64),
+    /// No `--fee`: pay `sat_per_byte * estimated_tx_size`.
+    Rate(u64),
+}
+
+impl FeeModel {
+    fn fee_for(&self, num_inputs: usize, num_outputs: usize) -> u64 {
+        match self {
+            FeeModel::Fixed(amount) => *amount,
+            FeeModel::Rate(sat_per_byte) => sat_per_byte * estimate_tx_size(num_inputs, num_outputs),
+        }
+    }
+}
+
+pub fn estimate_tx_size(num_inputs: usize, num_outputs: usize) -> u64 {
+    ESTIMATED_BASE_BYTES
+        + ESTIMATED_BYTES_PER_INPUT * num_inputs as u64
+        + ESTIMATED_BYTES_PER_OUTPUT * num_outputs as u64
+}
+
+#[derive(Debug, Clone, Serialize)]
+pub struct CoinSelection {
+    pub selected: Vec<Utxo>,
+    pub total_in: u64,
+    pub fee: u64,
+    pub change: u64,
+}
+
+/// Select UTXOs from `available` covering `target` (before fee) plus
+/// `fee_model`'s fee, with `num_outputs_without_change` non-change outputs
+/// (normally 1, the recipient).
+pub fn select_coins(
+    available: &[Utxo],
+    target: u64,
+    fee_model: FeeModel,
+    num_outputs_without_change: usize,
+) -> anyhow::Result<CoinSelection> {
+    if let Some(selection) = branch_and_bound(available, target, fee_model, num_outputs_without_change) {
+        return Ok(selection);
+    }
+    largest_first(available, target, fee_model, num_outputs_without_change)
+        .ok_or_else(|| anyhow::anyhow!("insufficient funds: no combination of UTXOs covers {target} plus fees"))
+}
+
+/// Changeless branch-and-bound: search for the smallest-overshoot subset
+/// whose value — net of each input's own marginal fee cost — lands between
+/// `target + base fee` and that plus the cost of a change output, so the
+/// wallet can skip creating change entirely.
+fn branch_and_bound(
+    available: &[Utxo],
+    target: u64,
+    fee_model: FeeModel,
+    num_outputs_without_change: usize,
+) -> Option<CoinSelection> {
+    let mut sorted: Vec<&Utxo> = available.iter().collect();
+    sorted.sort_by(|a, b| b.value.cmp(&a.value));
+
+    let base_fee = fee_model.fee_for(0, num_outputs_without_change);
+    let marginal_input_fee = fee_model
+        .fee_for(1, num_outputs_without_change)
+        .saturating_sub(base_fee);
+    let cost_of_change = match fee_model {
+        FeeModel::Fixed(_) => 0,
+        FeeModel::Rate(rate) => rate * ESTIMATED_BYTES_PER_OUTPUT,
+    };
+    let target_low = target.saturating_add(base_fee);
+    let target_high = target_low.saturating_add(cost_of_change);
+
+    let effective_values: Vec<u64> = sorted
+        .iter()
+        .map(|u| u.value.saturating_sub(marginal_input_fee))
+        .collect();
+
+    let mut best: Option<(Vec<usize>, u64)> = None;
+    let mut nodes = 0usize;
+    search(
+        &effective_values,
+        0,
+        &mut Vec::new(),
+        0,
+        target_low,
+        target_high,
+        &mut best,
+        &mut nodes,
+    );
+
+    let (indices, _) = best?;
+    let selected: Vec<Utxo> = indices.into_iter().map(|i| sorted[i].clone()).collect();
+    price_selection(selected, target, fee_model, num_outputs_without_change)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn search(
+    effective_values: &[u64],
+    index: usize,
+    selected: &mut Vec<usize>,
+    current_sum: u64,
+    target_low: u64,
+    target_high: u64,
+    best: &mut Option<(Vec<usize>, u64)>,
+    nodes: &mut usize,
+) {
+    *nodes += 1;
+    if *nodes > MAX_SEARCH_NODES {
+        return;
+    }
+    if current_sum >= target_low && current_sum <= target_high {
+        let is_better = best.as_ref().map_or(true, |(_, best_sum)| current_sum < *best_sum);
+        if is_better {
+            *best = Some((selected.clone(), current_sum));
+        }
+    }
+    if index >= effective_values.len() || current_sum > target_high {
+        return;
+    }
+
+    selected.push(index);
+    search(
+        effective_values,
+        index + 1,
+        selected,
+        current_sum + effective_values[index],
+        target_low,
+        target_high,
+        best,
+        nodes,
+    );
+    selected.pop();
+
+    search(
+        effective_values,
+        index + 1,
+        selected,
+        current_sum,
+        target_low,
+        target_high,
+        best,
+        nodes,
+    );
+}
+
+/// Legacy fallback: accumulate UTXOs largest-first, re-pricing the fee after
+/// each addition, until the running total covers `target` plus fee.
+fn largest_first(
+    available: &[Utxo],
+    target: u64,
+    fee_model: FeeModel,
+    num_outputs_without_change: usize,
+) -> Option<CoinSelection> {
+    let mut sorted: Vec<Utxo> = available.to_vec();
+    sorted.sort_by(|a, b| b.value.cmp(&a.value));
+
+    let mut selected = Vec::new();
+    let mut total_in: u64 = 0;
+    for utxo in sorted {
+        total_in += utxo.value;
+        selected.push(utxo);
+
+        let fee_with_change = fee_model.fee_for(selected.len(), num_outputs_without_change + 1);
+        if total_in >= target.saturating_add(fee_with_change) {
+            return price_selection(selected, target, fee_model, num_outputs_without_change);
+        }
+    }
+    None
+}
+
+/// Price a concrete set of inputs: if the leftover after a real change
+/// output would be dust, fold it into the fee instead of creating one.
+fn price_selection(
+    selected: Vec<Utxo>,
+    target: u64,
+    fee_model: FeeModel,
+    num_outputs_without_change: usize,
+) -> Option<CoinSelection> {
+    let total_in: u64 = selected.iter().map(|u| u.value).sum();
+    let fee_with_change = fee_model.fee_for(selected.len(), num_outputs_without_change + 1);
+    let fee_without_change = fee_model.fee_for(selected.len(), num_outputs_without_change);
+
+    if total_in < target.saturating_add(fee_without_change) {
+        return None;
+    }
+
+    let change = total_in.saturating_sub(target.saturating_add(fee_with_change));
+    Some(if change > DUST_THRESHOLD {
+        CoinSelection {
+            selected,
+            total_in,
+            fee: fee_with_change,
+            change,
+        }
+    } else {
+        CoinSelection {
+            selected,
+            total_in,
+            fee: total_in - target,
+            change: 0,
+        }
+    })
+}
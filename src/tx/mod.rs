@@ -0,0 +1,5 @@
+pub mod builder;
+pub mod coin_select;
+pub mod model;
+pub mod psbt;
+pub mod signer;
@@ -27,3 +27,13 @@ pub struct Transaction {
     pub vout: Vec<TxOut>,
     pub witness: Option<String>, // Dilithium5 sig (base64)
 }
+
+impl Transaction {
+    /// Canonical bytes to sign/verify: the transaction with `witness` cleared,
+    /// so the signature never covers itself.
+    pub fn signing_bytes(&self) -> anyhow::Result<Vec<u8>> {
+        let mut unsigned = self.clone();
+        unsigned.witness = None;
+        Ok(serde_json::to_vec(&unsigned)?)
+    }
+}
@@ -0,0 +1,65 @@
+//! Partially-signed transaction container (analogous to BIP174/PSBT) for a
+//! watch-only online wallet to hand an unsigned transaction to an
+//! air-gapped signer and get back a `witness`-filled one.
+
+use anyhow::Result;
+use base64::{engine::general_purpose, Engine as _};
+use secrecy::SecretBox;
+use serde::{Deserialize, Serialize};
+
+use super::model::{Transaction, TxOut};
+use super::signer::{sign_tx, verify_transaction};
+
+/// The UTXO and signing key info needed offline for one input, since the
+/// offline signer has no RPC access to look these up itself.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct InputMeta {
+    pub prevout: TxOut,
+    pub public_key_b64: String, // base64 Dilithium5 public key
+}
+
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct PartialTx {
+    pub tx: Transaction,
+    pub inputs_meta: Vec<InputMeta>,
+    pub finalized: bool,
+}
+
+/// Build an unsigned PSBT from a freshly built transaction and its inputs' metadata.
+pub fn create_psbt(tx: Transaction, inputs_meta: Vec<InputMeta>) -> PartialTx {
+    PartialTx {
+        tx,
+        inputs_meta,
+        finalized: false,
+    }
+}
+
+/// Sign a PSBT offline: fills `tx.witness` using `sk_secret` without ever
+/// touching the network.
+pub fn sign_psbt(psbt: &mut PartialTx, sk_secret: &SecretBox<Vec<u8>>) -> Result<()> {
+    if psbt.finalized {
+        return Err(anyhow::anyhow!("psbt is already finalized"));
+    }
+    let tx_bytes = psbt.tx.signing_bytes()?;
+    psbt.tx.witness = Some(sign_tx(sk_secret, &tx_bytes)?);
+    Ok(())
+}
+
+/// Validate the PSBT's signature against every input's recorded public key
+/// and produce the broadcast-ready `Transaction`.
+pub fn finalize_psbt(psbt: &mut PartialTx) -> Result<Transaction> {
+    if psbt.tx.witness.is_none() {
+        return Err(anyhow::anyhow!("psbt has not been signed yet"));
+    }
+    for meta in &psbt.inputs_meta {
+        let pk_bytes = general_purpose::STANDARD.decode(&meta.public_key_b64)?;
+        if !verify_transaction(&psbt.tx, &pk_bytes)? {
+            return Err(anyhow::anyhow!(
+                "psbt signature does not verify for input {}",
+                meta.prevout.address
+            ));
+        }
+    }
+    psbt.finalized = true;
+    Ok(psbt.tx.clone())
+}
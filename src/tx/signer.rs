@@ -1,15 +1,45 @@
 use base64::Engine as _;
 
-use pqcrypto_traits::sign::{DetachedSignature as _, SecretKey as _};
+use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _, SecretKey as _};
 
 /// Sign raw transaction bytes with Dilithium5 and return base64 signature.
 /// Accepts secret key as SecretVec<u8> to avoid raw copies in call sites.
 use secrecy::{ExposeSecret, SecretBox};
 
+use super::model::Transaction;
+
 pub fn sign_tx(sk_secret: &SecretBox<Vec<u8>>, tx_bytes: &[u8]) -> anyhow::Result<String> {
     let sk_bytes = sk_secret.expose_secret();
-    let sk = pqcrypto_dilithium::dilithium5::SecretKey::from_bytes(sk_bytes)
-        .map_err(|_| anyhow::anyhow!("invalid secret key"))?;
+    let sk = pqcrypto_dilithium::dilithium5::SecretKey::from_bytes(sk_bytes).map_err(|_| {
+        anyhow::anyhow!(
+            "invalid secret key: expected a 4896-byte Dilithium5 secret key, got {}; this \
+             wallet's key may have been generated by a non-seeded or outdated keygen path",
+            sk_bytes.len()
+        )
+    })?;
     let sig = pqcrypto_dilithium::dilithium5::detached_sign(tx_bytes, &sk);
     Ok(base64::engine::general_purpose::STANDARD.encode(sig.as_bytes()))
 }
+
+/// Verify a base64 Dilithium5 signature over `tx_bytes` against `pk_bytes`.
+/// Returns `Ok(false)` for a mismatched signature, and `Err` only for
+/// malformed input (bad base64, bad key/signature encoding).
+pub fn verify_tx(pk_bytes: &[u8], tx_bytes: &[u8], sig_b64: &str) -> anyhow::Result<bool> {
+    let sig_bytes = base64::engine::general_purpose::STANDARD.decode(sig_b64)?;
+    let pk = pqcrypto_dilithium::dilithium5::PublicKey::from_bytes(pk_bytes)
+        .map_err(|_| anyhow::anyhow!("invalid public key"))?;
+    let sig = pqcrypto_dilithium::dilithium5::DetachedSignature::from_bytes(&sig_bytes)
+        .map_err(|_| anyhow::anyhow!("invalid signature encoding"))?;
+    Ok(pqcrypto_dilithium::dilithium5::verify_detached_signature(&sig, tx_bytes, &pk).is_ok())
+}
+
+/// Verify `tx.witness` against `pk_bytes`, serializing the transaction the
+/// same way `sign_tx` callers do. Returns `Ok(false)` if `witness` is unset
+/// or the signature doesn't match.
+pub fn verify_transaction(tx: &Transaction, pk_bytes: &[u8]) -> anyhow::Result<bool> {
+    let Some(sig_b64) = &tx.witness else {
+        return Ok(false);
+    };
+    let tx_bytes = tx.signing_bytes()?;
+    verify_tx(pk_bytes, &tx_bytes, sig_b64)
+}
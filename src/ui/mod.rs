@@ -132,6 +132,27 @@ pub fn show_balance(balance: &str, pending: &str) {
     println!();
 }
 
+/// Display the `getwalletinfo`-style trusted/untrusted-pending/immature breakdown
+pub fn show_wallet_info(
+    walletname: &str,
+    txcount: usize,
+    trusted: &str,
+    untrusted_pending: &str,
+    immature: &str,
+) {
+    println!();
+    println!("{}", "╔══════════════════════════════════════════════════════╗".bright_blue());
+    println!("{}", "║                    WALLET INFO                      ║".bright_blue().bold());
+    println!("{}", "╠══════════════════════════════════════════════════════╣".bright_blue());
+    show_table_row("Wallet", walletname);
+    show_table_row("Tx Count", &txcount.to_string());
+    show_table_row_colored("Trusted", trusted, Color::Green);
+    show_table_row_colored("Untrusted Pending", untrusted_pending, Color::Yellow);
+    show_table_row_colored("Immature", immature, Color::BrightBlack);
+    println!("{}", "╚══════════════════════════════════════════════════════╝".bright_blue());
+    println!();
+}
+
 /// Display a transaction history table
 pub fn show_transaction_history(transactions: Vec<(&str, &str, &str, &str)>) {
     println!();
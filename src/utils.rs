@@ -31,3 +31,9 @@ pub fn parse_amount_to_units(s: &str) -> anyhow::Result<u64> {
 pub fn zeroize_vec(mut v: Vec<u8>) {
     v.zeroize();
 }
+
+/// Format base units back into an 8-decimal BTP string (inverse of
+/// `parse_amount_to_units`).
+pub fn format_units_as_btp(units: u64) -> String {
+    format!("{}.{:08}", units / 100_000_000, units % 100_000_000)
+}
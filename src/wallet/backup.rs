@@ -0,0 +1,121 @@
+//! Encrypted multi-account backup bundle: bundles several wallets' portable
+//! identity (address, derivation path, seed phrase) into one Argon2id +
+//! XChaCha20-Poly1305-encrypted file, so moving accounts between machines
+//! never leaves a seed phrase sitting in plaintext on disk (unlike
+//! `ops::backup_wallet`, which just copies the wallet JSON as-is).
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::wallet::keystore::{default_kdf_params, derive_key, KdfParams, Password};
+use crate::wallet::ops::{wallet_from_seed_phrase, Wallet};
+
+/// Identifies a file as a BTPC wallet backup bundle, checked before any
+/// decryption is attempted so a wrong or corrupted file fails fast.
+const MAGIC: &str = "BTPC-WALLET-BACKUP";
+/// Current backup container format; `import_backup` rejects any other
+/// version so a future format change can add a migration path instead of
+/// silently misparsing.
+const FORMAT_VERSION: u32 = 1;
+
+/// One account's portable identity inside a backup bundle.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct BackupEntry {
+    address: String,
+    derivation_path: String,
+    seed_phrase: String,
+}
+
+impl From<&Wallet> for BackupEntry {
+    fn from(w: &Wallet) -> Self {
+        BackupEntry {
+            address: w.address.clone(),
+            derivation_path: w.derivation_path.clone(),
+            seed_phrase: w.seed_phrase.clone(),
+        }
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct BackupFile {
+    magic: String,
+    version: u32,
+    kdf: KdfParams,
+    salt_b64: String,
+    nonce_b64: String,
+    ciphertext_b64: String,
+}
+
+/// Encrypt `wallets` into a versioned, passphrase-protected backup bundle at `path`.
+pub fn export_backup(wallets: &[Wallet], path: &Path, passphrase: &Password) -> Result<()> {
+    let entries: Vec<BackupEntry> = wallets.iter().map(BackupEntry::from).collect();
+    let plaintext = serde_json::to_vec(&entries)?;
+
+    let mut salt = [0u8; 16];
+    getrandom::getrandom(&mut salt)?;
+    let kdf = default_kdf_params();
+    let key_bytes = derive_key(passphrase, &salt, kdf.m, kdf.t, kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let mut nonce = [0u8; 24];
+    getrandom::getrandom(&mut nonce)?;
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), plaintext.as_slice())
+        .map_err(|e| anyhow!("encrypt backup: {e}"))?;
+
+    let file = BackupFile {
+        magic: MAGIC.to_string(),
+        version: FORMAT_VERSION,
+        kdf,
+        salt_b64: general_purpose::STANDARD.encode(salt),
+        nonce_b64: general_purpose::STANDARD.encode(nonce),
+        ciphertext_b64: general_purpose::STANDARD.encode(ciphertext),
+    };
+    fs::write(path, serde_json::to_string_pretty(&file)?)?;
+    Ok(())
+}
+
+/// Decrypt a backup bundle produced by `export_backup`, re-deriving each
+/// wallet's real keypair from its seed phrase (assuming the default, empty
+/// BIP-39 passphrase; a wallet created with a non-empty mnemonic passphrase
+/// must be re-keyed by the caller via `ops::wallet_from_seed_phrase`).
+/// Restored wallets carry `balance = 0` — backups hold identity, not chain
+/// state, so the caller should rescan after import.
+pub fn import_backup(path: &Path, passphrase: &Password) -> Result<Vec<Wallet>> {
+    let data = fs::read_to_string(path)?;
+    let file: BackupFile = serde_json::from_str(&data)?;
+
+    if file.magic != MAGIC {
+        return Err(anyhow!("not a BTPC wallet backup file"));
+    }
+    if file.version != FORMAT_VERSION {
+        return Err(anyhow!(
+            "unsupported backup format version {} (expected {})",
+            file.version,
+            FORMAT_VERSION
+        ));
+    }
+
+    let salt = general_purpose::STANDARD.decode(&file.salt_b64)?;
+    let key_bytes = derive_key(passphrase, &salt, file.kdf.m, file.kdf.t, file.kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let nonce = general_purpose::STANDARD.decode(&file.nonce_b64)?;
+    let ciphertext = general_purpose::STANDARD.decode(&file.ciphertext_b64)?;
+    let plaintext = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("wrong password or corrupted backup (auth tag mismatch)"))?;
+
+    let entries: Vec<BackupEntry> = serde_json::from_slice(&plaintext)?;
+    entries
+        .into_iter()
+        .map(|e| wallet_from_seed_phrase(&e.seed_phrase, "", &e.derivation_path))
+        .collect()
+}
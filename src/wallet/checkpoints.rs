@@ -0,0 +1,43 @@
+//! Hardcoded (height, block hash) checkpoints per network. `wallet::scan`
+//! starts a rescan from the most recent checkpoint at or below the target
+//! height instead of genesis, so a wallet with a recent birthday doesn't
+//! have to walk the entire chain to find its first transaction.
+//!
+//! Both tables below are currently **empty**: this chain is still young
+//! enough that there's nothing yet worth hardcoding a verified checkpoint
+//! against. `nearest_checkpoint` returns `None` in that case rather than a
+//! placeholder genesis entry -- a genesis "checkpoint" would always match
+//! (height 0 is `<=` every height), silently forcing every rescan back to
+//! block 0 and defeating the entire point of recording a wallet birthday.
+//! Callers fall back to their own best-known starting point (see
+//! `wallet::scan::scan_wallet`'s use of `wallet.birthday_height`) instead.
+//! Real entries get appended here, with hashes worth verifying against, as
+//! the chain matures.
+
+/// A known-good block at `height`, used only to pick a scan starting point;
+/// the hash isn't currently verified against the node's answer.
+#[derive(Debug, Clone, Copy)]
+pub struct Checkpoint {
+    pub height: u64,
+    pub hash: &'static str,
+}
+
+const MAINNET_CHECKPOINTS: &[Checkpoint] = &[];
+
+const TESTNET_CHECKPOINTS: &[Checkpoint] = &[];
+
+fn table_for(network: &str) -> &'static [Checkpoint] {
+    match network {
+        "mainnet" => MAINNET_CHECKPOINTS,
+        _ => TESTNET_CHECKPOINTS,
+    }
+}
+
+/// The latest recorded checkpoint at or below `height` for `network`, or
+/// `None` if none has been recorded yet (currently always, since both
+/// tables are empty -- see module docs). Callers must not treat `None` as
+/// "start from genesis"; fall back to a real starting point such as the
+/// wallet's birthday height instead.
+pub fn nearest_checkpoint(network: &str, height: u64) -> Option<Checkpoint> {
+    table_for(network).iter().rev().find(|c| c.height <= height).copied()
+}
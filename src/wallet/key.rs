@@ -1,10 +1,11 @@
-use crate::utils::hex_lower;
+use crate::wallet::ops::{encode_address, BECH32_CHARSET};
 use pqcrypto_dilithium::dilithium5::{
     keypair, PublicKey as DilithiumPublicKey, SecretKey as DilithiumSecretKey,
 };
 use pqcrypto_traits::sign::PublicKey;
 use pqcrypto_traits::sign::SecretKey;
-use sha2::{Digest, Sha512};
+use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
+use std::sync::Arc;
 
 pub struct Keypair {
     pub pk: Vec<u8>,
@@ -19,7 +20,111 @@ pub fn generate_keypair() -> Keypair {
     }
 }
 
+/// Derive a wallet's canonical Bech32m address from its Dilithium5 public
+/// key -- the same encoding `validate_address` checks, so every
+/// address-producing path (random keygen, seed/HD/mnemonic-derived, vanity)
+/// yields an address the rest of the wallet accepts.
 pub fn derive_address_from_pk(pk: &[u8]) -> String {
-    let digest = Sha512::digest(pk);
-    hex_lower(&digest)
+    encode_address("btpc", pk)
+}
+
+/// A vanity keypair match: the address's data part satisfied the requested pattern.
+pub struct VanityMatch {
+    pub keypair: Keypair,
+    pub address: String,
+    pub attempts: u64,
+}
+
+/// Ensure `pattern` only uses characters from the Bech32 charset, apart from
+/// at most one `*` prefix/suffix separator, so a mistyped/impossible pattern
+/// is rejected up front instead of spinning Dilithium keygen forever.
+fn validate_vanity_pattern(pattern: &str) -> anyhow::Result<()> {
+    if pattern.is_empty() {
+        return Err(anyhow::anyhow!("vanity pattern must not be empty"));
+    }
+    if pattern.matches('*').count() > 1 {
+        return Err(anyhow::anyhow!("vanity pattern may contain at most one '*'"));
+    }
+    for c in pattern.chars().filter(|&c| c != '*') {
+        if !BECH32_CHARSET.contains(&(c as u8)) {
+            return Err(anyhow::anyhow!(
+                "vanity pattern char '{}' is not in the Bech32 charset",
+                c
+            ));
+        }
+    }
+    Ok(())
+}
+
+/// Mine a Dilithium5 keypair whose Bech32m address data part matches
+/// `pattern`. `pattern` may contain a single `*` to separate a required
+/// prefix from a required suffix (e.g. `"abc*xyz"`); without a `*` the whole
+/// pattern is treated as a prefix. Searches across `max_threads` worker
+/// threads and stops all of them as soon as one finds a match.
+pub fn generate_keypair_with_prefix(pattern: &str, max_threads: usize) -> anyhow::Result<VanityMatch> {
+    generate_keypair_with_prefix_cb(pattern, max_threads, |_attempts| {}, None)
+}
+
+/// Like [`generate_keypair_with_prefix`], but reports attempts via
+/// `on_progress` and can be stopped early via a shared `cancel` flag.
+pub fn generate_keypair_with_prefix_cb(
+    pattern: &str,
+    max_threads: usize,
+    on_progress: impl Fn(u64) + Send + Sync + 'static,
+    cancel: Option<Arc<AtomicBool>>,
+) -> anyhow::Result<VanityMatch> {
+    validate_vanity_pattern(pattern)?;
+    let (prefix, suffix) = match pattern.split_once('*') {
+        Some((p, s)) => (p.to_string(), Some(s.to_string())),
+        None => (pattern.to_string(), None),
+    };
+
+    let max_threads = max_threads.max(1);
+    let cancel = cancel.unwrap_or_else(|| Arc::new(AtomicBool::new(false)));
+    let attempts = Arc::new(AtomicU64::new(0));
+    let found: Arc<std::sync::Mutex<Option<VanityMatch>>> = Arc::new(std::sync::Mutex::new(None));
+    let on_progress = Arc::new(on_progress);
+
+    std::thread::scope(|scope| {
+        for _ in 0..max_threads {
+            let cancel = cancel.clone();
+            let attempts = attempts.clone();
+            let found = found.clone();
+            let on_progress = on_progress.clone();
+            let prefix = prefix.clone();
+            let suffix = suffix.clone();
+            scope.spawn(move || {
+                while !cancel.load(Ordering::Relaxed) {
+                    let kp = generate_keypair();
+                    let address = encode_address("btpc", &kp.pk);
+                    let n = attempts.fetch_add(1, Ordering::Relaxed) + 1;
+                    on_progress(n);
+
+                    let data_part = address.rsplit_once('1').map(|(_, d)| d).unwrap_or(&address);
+                    let matches = data_part.starts_with(&prefix)
+                        && suffix
+                            .as_ref()
+                            .map_or(true, |s| data_part.ends_with(s.as_str()));
+                    if matches {
+                        let mut guard = found.lock().unwrap();
+                        if guard.is_none() {
+                            *guard = Some(VanityMatch {
+                                keypair: kp,
+                                address,
+                                attempts: n,
+                            });
+                        }
+                        cancel.store(true, Ordering::Relaxed);
+                        return;
+                    }
+                }
+            });
+        }
+    });
+
+    found
+        .lock()
+        .unwrap()
+        .take()
+        .ok_or_else(|| anyhow::anyhow!("vanity search cancelled before a match was found"))
 }
@@ -5,8 +5,13 @@ use chacha20poly1305::{
     aead::{Aead, KeyInit},
     ChaCha20Poly1305, Key, Nonce,
 };
+use secrecy::{ExposeSecret, SecretBox};
 use serde::{Deserialize, Serialize};
 
+/// A passphrase that is wiped from memory once it goes out of scope, used by
+/// every password-accepting function in this module.
+pub type Password = SecretBox<String>;
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct KdfParams {
     pub m: u32,
@@ -19,22 +24,6 @@ fn kdf_name() -> String {
     "argon2id".into()
 }
 
-#[derive(Debug, Clone, Serialize, Deserialize)]
-pub struct WalletFile {
-    pub version: u32,
-    pub network: String,
-    pub address: String,
-    pub public_key: String,     // base64
-    pub secret_key_enc: String, // base64
-    pub cipher: String,         // chacha20poly1305
-    pub kdf: KdfParams,
-    pub nonce: String, // base64
-    pub balance_cached: u64,
-    pub last_scanned_height: u64,
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub created_at: Option<String>,
-}
-
 pub struct EncBundle {
     pub salt: Vec<u8>,
     pub nonce: [u8; 12],
@@ -50,7 +39,7 @@ pub fn default_kdf_params() -> KdfParams {
     }
 }
 
-pub fn derive_key(passphrase: &str, salt: &[u8], m: u32, t: u32, p: u32) -> Result<[u8; 32]> {
+pub fn derive_key(passphrase: &Password, salt: &[u8], m: u32, t: u32, p: u32) -> Result<[u8; 32]> {
     // Use Argon2id with std feature enabled, convert errors to anyhow
     let params = argon2::Params::new(m, t, p, None)
         .map_err(|e| anyhow::anyhow!(format!("argon2 params: {e}")))?;
@@ -63,12 +52,12 @@ pub fn derive_key(passphrase: &str, salt: &[u8], m: u32, t: u32, p: u32) -> Resu
     .map_err(|e| anyhow::anyhow!(format!("argon2 ctx: {e}")))?;
     let mut key = [0u8; 32];
     argon2
-        .hash_password_into(passphrase.as_bytes(), salt, &mut key)
+        .hash_password_into(passphrase.expose_secret().as_bytes(), salt, &mut key)
         .map_err(|e| anyhow::anyhow!(format!("argon2 derive: {e}")))?;
     Ok(key)
 }
 
-pub fn encrypt_sk_with_params(passphrase: &str, sk: &[u8], kdf: &KdfParams) -> Result<EncBundle> {
+pub fn encrypt_sk_with_params(passphrase: &Password, sk: &[u8], kdf: &KdfParams) -> Result<EncBundle> {
     let mut salt = [0u8; 16];
     getrandom::getrandom(&mut salt)?;
     let key = derive_key(passphrase, &salt, kdf.m, kdf.t, kdf.p)?;
@@ -85,21 +74,25 @@ pub fn encrypt_sk_with_params(passphrase: &str, sk: &[u8], kdf: &KdfParams) -> R
     })
 }
 
-pub fn encrypt_sk(passphrase: &str, sk: &[u8]) -> Result<EncBundle> {
+pub fn encrypt_sk(passphrase: &Password, sk: &[u8]) -> Result<EncBundle> {
     encrypt_sk_with_params(passphrase, sk, &default_kdf_params())
 }
 
-/// Decrypt using base64-encoded salt string and raw nonce/ciphertext
+/// Decrypt using base64-encoded salt string and raw nonce/ciphertext by
+/// brute-forcing a hardcoded list of legacy KDF parameter sets.
+///
+/// Deprecated: wallets now persist their own `KdfParams` directly on
+/// `Wallet` and are decrypted via `wallet::lock::unlock_wallet`, which knows
+/// the exact params to use instead of guessing. Kept only so wallets saved
+/// before KDF params were recorded can still be opened.
+#[deprecated(note = "use wallet::lock::unlock_wallet with the wallet's own persisted KdfParams instead")]
 pub fn decrypt_sk(
-    password: &str,
+    password: &Password,
     salt_str: &str,
     nonce_bytes: &[u8; 12],
     ciphertext: &[u8],
 ) -> Result<Vec<u8>> {
     let salt = general_purpose::STANDARD.decode(salt_str)?;
-    // We don't know the KDF params here; caller must supply correct params for derive.
-    // For wallet file decryption, caller should read KDF (m,t,p) from JSON and pass via derive.
-    // For backward compatibility with our ops helper, try default params first; if it fails, try a small fallback set.
     let try_params = [
         default_kdf_params(),
         KdfParams {
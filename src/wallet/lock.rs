@@ -0,0 +1,123 @@
+//! Real at-rest wallet encryption: password lock/unlock/decrypt lifecycle
+//! for [`Wallet`], using Argon2id + XChaCha20-Poly1305 in place of the
+//! irreversible SHA-512 placeholder `encrypted_private_key` used to hold.
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use secrecy::{ExposeSecret, SecretBox};
+use std::time::{Duration, Instant};
+
+use crate::wallet::keystore::{default_kdf_params, derive_key, Password};
+use crate::wallet::ops::Wallet;
+
+/// How long an [`UnlockedWallet`] is considered valid before callers must re-unlock.
+pub const DEFAULT_UNLOCK_TTL: Duration = Duration::from_secs(5 * 60);
+
+/// In-memory-only view of a wallet's secret material after a successful unlock.
+/// Never serialized; dropped (and zeroized) once it goes out of scope.
+pub struct UnlockedWallet {
+    pub secret_key: SecretBox<Vec<u8>>,
+    pub seed_phrase: SecretBox<String>,
+    unlocked_at: Instant,
+    ttl: Duration,
+}
+
+impl UnlockedWallet {
+    pub fn is_expired(&self) -> bool {
+        self.unlocked_at.elapsed() > self.ttl
+    }
+}
+
+/// Encrypt `wallet`'s secret key and seed phrase in place with `passphrase`,
+/// setting `encrypted = true`. Never leaves plaintext and ciphertext both
+/// stored: callers must pass the plaintext `secret_key` once, here.
+pub fn encrypt_wallet(wallet: &mut Wallet, secret_key: &[u8], passphrase: &Password) -> Result<()> {
+    let mut salt = [0u8; 16];
+    getrandom::getrandom(&mut salt)?;
+    let kdf = default_kdf_params();
+    let key_bytes = derive_key(passphrase, &salt, kdf.m, kdf.t, kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let mut sk_nonce = [0u8; 24];
+    getrandom::getrandom(&mut sk_nonce)?;
+    let sk_ct = cipher
+        .encrypt(XNonce::from_slice(&sk_nonce), secret_key)
+        .map_err(|e| anyhow!("encrypt secret key: {e}"))?;
+
+    let mut seed_nonce = [0u8; 24];
+    getrandom::getrandom(&mut seed_nonce)?;
+    let seed_ct = cipher
+        .encrypt(XNonce::from_slice(&seed_nonce), wallet.seed_phrase.as_bytes())
+        .map_err(|e| anyhow!("encrypt seed phrase: {e}"))?;
+
+    wallet.encrypted_private_key = general_purpose::STANDARD.encode(sk_ct);
+    wallet.seed_phrase = general_purpose::STANDARD.encode(seed_ct);
+    wallet.salt_b64 = Some(general_purpose::STANDARD.encode(salt));
+    wallet.nonce_b64 = Some(general_purpose::STANDARD.encode(sk_nonce));
+    wallet.seed_nonce_b64 = Some(general_purpose::STANDARD.encode(seed_nonce));
+    wallet.kdf = Some(kdf);
+    wallet.encrypted = true;
+    Ok(())
+}
+
+/// Decrypt into an in-memory-only [`UnlockedWallet`], valid for `ttl`
+/// (defaults to [`DEFAULT_UNLOCK_TTL`]). Fails loudly on a wrong password
+/// (AEAD auth-tag mismatch) rather than returning garbage.
+pub fn unlock_wallet(
+    wallet: &Wallet,
+    passphrase: &Password,
+    ttl: Option<Duration>,
+) -> Result<UnlockedWallet> {
+    if !wallet.encrypted {
+        return Err(anyhow!("wallet is not encrypted"));
+    }
+    let kdf = wallet.kdf.clone().ok_or_else(|| anyhow!("wallet is missing its kdf params"))?;
+    let salt_b64 = wallet.salt_b64.as_ref().ok_or_else(|| anyhow!("wallet is missing its salt"))?;
+    let nonce_b64 = wallet.nonce_b64.as_ref().ok_or_else(|| anyhow!("wallet is missing its nonce"))?;
+    let seed_nonce_b64 = wallet
+        .seed_nonce_b64
+        .as_ref()
+        .ok_or_else(|| anyhow!("wallet is missing its seed nonce"))?;
+
+    let salt = general_purpose::STANDARD.decode(salt_b64)?;
+    let key_bytes = derive_key(passphrase, &salt, kdf.m, kdf.t, kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let sk_nonce = general_purpose::STANDARD.decode(nonce_b64)?;
+    let sk_ct = general_purpose::STANDARD.decode(&wallet.encrypted_private_key)?;
+    let secret_key = cipher
+        .decrypt(XNonce::from_slice(&sk_nonce), sk_ct.as_slice())
+        .map_err(|_| anyhow!("wrong password or corrupted wallet (secret key auth tag mismatch)"))?;
+
+    let seed_nonce = general_purpose::STANDARD.decode(seed_nonce_b64)?;
+    let seed_ct = general_purpose::STANDARD.decode(&wallet.seed_phrase)?;
+    let seed_bytes = cipher
+        .decrypt(XNonce::from_slice(&seed_nonce), seed_ct.as_slice())
+        .map_err(|_| anyhow!("wrong password or corrupted wallet (seed phrase auth tag mismatch)"))?;
+    let seed_phrase = String::from_utf8(seed_bytes)?;
+
+    Ok(UnlockedWallet {
+        secret_key: SecretBox::new(Box::new(secret_key)),
+        seed_phrase: SecretBox::new(Box::new(seed_phrase)),
+        unlocked_at: Instant::now(),
+        ttl: ttl.unwrap_or(DEFAULT_UNLOCK_TTL),
+    })
+}
+
+/// Permanently strip encryption, writing the plaintext secret key (as lowercase
+/// hex, matching the old placeholder's format) and seed phrase back into `wallet`.
+pub fn decrypt_wallet(wallet: &mut Wallet, passphrase: &Password) -> Result<()> {
+    let unlocked = unlock_wallet(wallet, passphrase, None)?;
+    wallet.encrypted_private_key = crate::utils::hex_lower(unlocked.secret_key.expose_secret());
+    wallet.seed_phrase = unlocked.seed_phrase.expose_secret().clone();
+    wallet.kdf = None;
+    wallet.salt_b64 = None;
+    wallet.nonce_b64 = None;
+    wallet.seed_nonce_b64 = None;
+    wallet.encrypted = false;
+    Ok(())
+}
@@ -1,44 +1,270 @@
+use crate::wallet::ops::encode_address;
+use anyhow::{anyhow, Result};
 use bip39::{Language, Mnemonic};
+use fips204::ml_dsa_87::KG;
+use fips204::traits::{KeyGen, SerDes};
 use hkdf::Hkdf;
-use sha2::{Digest, Sha512};
+use hmac::{Hmac, Mac};
+use secrecy::SecretBox;
+use sha2::Sha512;
+use zeroize::{Zeroize, Zeroizing};
+
+type HmacSha512 = Hmac<Sha512>;
 
 /// Create a 24-word mnemonic using standard BIP-39 English wordlist.
 pub fn generate_mnemonic_24() -> Mnemonic {
-    Mnemonic::generate_in(Language::English, 24).expect("entropy gen")
+    generate_mnemonic(24, Language::English).expect("24 words is a valid BIP-39 word count")
+}
+
+/// Map a BIP-39 word count to its entropy size in bits, rejecting anything
+/// BIP-39 doesn't define (it only specifies 12/15/18/21/24 words, i.e.
+/// 128-256 bits of entropy in 32-bit steps with an attached checksum).
+fn entropy_bits_for_word_count(word_count: usize) -> Result<usize> {
+    match word_count {
+        12 => Ok(128),
+        15 => Ok(160),
+        18 => Ok(192),
+        21 => Ok(224),
+        24 => Ok(256),
+        other => Err(anyhow!(
+            "invalid mnemonic word count {other}: BIP-39 only supports 12, 15, 18, 21, or 24 words"
+        )),
+    }
+}
+
+/// Builder for BIP-39 mnemonic generation, so callers aren't stuck with
+/// `generate_mnemonic_24`'s hardcoded 24-word English default. `word_count`
+/// must be one of BIP-39's defined sizes (12/15/18/21/24); `language` selects
+/// the wordlist. The wallet's recovery and derivation paths in `wallet::ops`
+/// (`validate_seed_phrase`, `recover_wallet_from_seed`,
+/// `derive_address_at_path`) parse phrases against the English wordlist
+/// only, so a mnemonic built in another language is rejected by them.
+pub struct MnemonicBuilder {
+    word_count: usize,
+    language: Language,
+}
+
+impl Default for MnemonicBuilder {
+    fn default() -> Self {
+        MnemonicBuilder {
+            word_count: 24,
+            language: Language::English,
+        }
+    }
+}
+
+impl MnemonicBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the word count; validated against BIP-39's defined sizes in `build`.
+    pub fn word_count(mut self, word_count: usize) -> Self {
+        self.word_count = word_count;
+        self
+    }
+
+    /// Set the wordlist language.
+    pub fn language(mut self, language: Language) -> Self {
+        self.language = language;
+        self
+    }
+
+    pub fn build(self) -> Result<Mnemonic> {
+        entropy_bits_for_word_count(self.word_count)?;
+        Mnemonic::generate_in(self.language, self.word_count)
+            .map_err(|e| anyhow!("failed to generate mnemonic: {e}"))
+    }
+}
+
+/// Convenience wrapper around `MnemonicBuilder` for the common case of
+/// picking just a word count and language.
+pub fn generate_mnemonic(word_count: usize, language: Language) -> Result<Mnemonic> {
+    MnemonicBuilder::new()
+        .word_count(word_count)
+        .language(language)
+        .build()
+}
+
+/// Why a recovery phrase was rejected. Callers that only want to propagate
+/// the failure can do so with `?` into an `anyhow::Result` (`anyhow::Error`
+/// implements `From` for any `std::error::Error`); callers that need to
+/// distinguish a bad word count from a bad checksum (e.g. to suggest "check
+/// for a typo" versus "count your words") can match on the variant instead
+/// of parsing an error string.
+#[derive(Debug)]
+pub enum WalletError {
+    /// `phrase` doesn't have one of BIP-39's defined word counts.
+    InvalidWordCount { got: usize },
+    /// Every word is in the wordlist's length class, but `Mnemonic::parse_in`
+    /// rejected it: an unknown word, or a checksum mismatch (the most common
+    /// cause: a typo'd or transposed word).
+    InvalidChecksum(String),
+}
+
+impl std::fmt::Display for WalletError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            WalletError::InvalidWordCount { got } => write!(
+                f,
+                "recovery phrase has {got} words; BIP-39 requires 12, 15, 18, 21, or 24"
+            ),
+            WalletError::InvalidChecksum(e) => write!(f, "invalid recovery phrase: {e}"),
+        }
+    }
+}
+
+impl std::error::Error for WalletError {}
+
+/// Parse and strictly validate a user-supplied recovery phrase against
+/// `language`'s wordlist. Word count is checked up front against BIP-39's
+/// defined sizes with a specific message; `Mnemonic::parse_in` then performs
+/// the real BIP-39 validation (every word is in the wordlist, and the
+/// trailing checksum bits match `SHA256` of the entropy), so a phrase with a
+/// typo'd word or transposed words is rejected here instead of silently
+/// importing as a different, valid-looking wallet.
+pub fn import_mnemonic(phrase: &str, language: Language) -> Result<Mnemonic, WalletError> {
+    let word_count = phrase.split_whitespace().count();
+    if entropy_bits_for_word_count(word_count).is_err() {
+        return Err(WalletError::InvalidWordCount { got: word_count });
+    }
+
+    Mnemonic::parse_in(language, phrase).map_err(|e| WalletError::InvalidChecksum(e.to_string()))
 }
 
-/// Convert mnemonic (and optional passphrase) to a 64-byte seed.
-pub fn mnemonic_to_seed(mnemonic: &Mnemonic, passphrase: Option<&str>) -> Vec<u8> {
+/// Convert mnemonic (and optional passphrase) to a 64-byte seed, wiped on
+/// drop since it's the root of every key this wallet can derive.
+pub fn mnemonic_to_seed(mnemonic: &Mnemonic, passphrase: Option<&str>) -> Zeroizing<Vec<u8>> {
     let pass = passphrase.unwrap_or("");
-    let seed = mnemonic.to_seed(pass);
-    seed.to_vec()
+    let mut seed = mnemonic.to_seed(pass);
+    let out = Zeroizing::new(seed.to_vec());
+    seed.zeroize();
+    out
 }
 
-/// Derive a Dilithium5-like keypair bytes and address from a mnemonic.
+/// Derive a genuine ML-DSA-87 (Dilithium5) keypair and address from a mnemonic.
 ///
 /// This is **deterministic**: the same mnemonic + passphrase will always yield
-/// the same pk/sk/address. Actual Dilithium5 signing uses pqcrypto with a
-/// proper SecretKey, but here we produce stable byte buffers for wallet restore.
+/// the same pk/sk/address. The 64-byte BIP-39 seed is reduced via HKDF to the
+/// 32-byte seed `ξ` FIPS 204 `KeyGen_internal` takes, then handed to
+/// `fips204`'s deterministic `KG::keygen_from_seed` — the same standardized
+/// ML-DSA-87 byte encoding `pqcrypto_dilithium::dilithium5` (used everywhere
+/// else this wallet signs and verifies, see `wallet::key`/`tx::signer`) reads
+/// back via `from_bytes`.
 pub fn derive_dilithium5_keypair_from_mnemonic(
     mnemonic: &Mnemonic,
     passphrase: Option<&str>,
-) -> (Vec<u8>, Vec<u8>, String) {
-    // Step 1: derive BIP39 seed
-    let seed = mnemonic.to_seed(passphrase.unwrap_or(""));
-
-    // Step 2: HKDF-SHA512 derive 64 bytes
-    let hk = Hkdf::<Sha512>::new(None, &seed);
-    let mut okm = [0u8; 64];
-    hk.expand(b"BTPC-DILITHIUM5-KEYGEN-v1", &mut okm)
+) -> (Vec<u8>, SecretBox<Vec<u8>>, String) {
+    let mut seed = mnemonic.to_seed(passphrase.unwrap_or(""));
+    let result = expand_dilithium5_keypair(&seed);
+    seed.zeroize();
+    result
+}
+
+/// HKDF-SHA512-reduce `key_seed` to the 32-byte FIPS 204 `KeyGen_internal`
+/// seed `ξ`, then run `fips204`'s deterministic ML-DSA-87 keygen to produce a
+/// real Dilithium5 `(pk, sk, address)` triple, with `address` the same
+/// Bech32m encoding every other address-producing path uses (see
+/// `wallet::key::derive_address_from_pk`), so a mnemonic-derived address
+/// passes `validate_address` like any other. The intermediate seed is
+/// scrubbed before returning, since it determines the wallet's secret key.
+fn expand_dilithium5_keypair(key_seed: &[u8]) -> (Vec<u8>, SecretBox<Vec<u8>>, String) {
+    let hk = Hkdf::<Sha512>::new(None, key_seed);
+    let mut xi = [0u8; 32];
+    hk.expand(b"BTPC-DILITHIUM5-KEYGEN-v1", &mut xi)
         .expect("HKDF expand");
 
-    // Step 3: split into pk/sk halves
-    let pk = okm[0..32].to_vec();
-    let sk = okm[32..64].to_vec();
+    let (pk, sk) = KG::keygen_from_seed(&xi);
+    xi.zeroize();
+
+    let pk_bytes = pk.into_bytes().to_vec();
+    let sk_bytes = SecretBox::new(Box::new(sk.into_bytes().to_vec()));
+    let addr = encode_address("btpc", &pk_bytes);
+
+    (pk_bytes, sk_bytes, addr)
+}
+
+fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new_from_slice(key).expect("HMAC accepts any key length");
+    mac.update(data);
+    let mut out = [0u8; 64];
+    out.copy_from_slice(&mac.finalize().into_bytes());
+    out
+}
+
+/// Parse one `44'`-style path component into its SLIP-0010 hardened index
+/// (`raw_index + 2^31`). Non-hardened components (no trailing `'`/`h`) are
+/// rejected: Dilithium5 has no public-key-only derivation to fall back on, so
+/// every component must be hardened.
+fn parse_hardened_index(component: &str) -> Result<u32> {
+    let digits = component
+        .strip_suffix('\'')
+        .or_else(|| component.strip_suffix('h'))
+        .ok_or_else(|| {
+            anyhow!(
+                "derivation path component {component:?} must be hardened (e.g. \"44'\"); \
+                 Dilithium5 keys have no non-hardened derivation"
+            )
+        })?;
+    let raw: u32 = digits
+        .parse()
+        .map_err(|_| anyhow!("invalid derivation path component {component:?}"))?;
+    raw.checked_add(1 << 31)
+        .ok_or_else(|| anyhow!("derivation path component {component:?} out of range"))
+}
+
+/// Split a `m/44'/0'/0'/0/5'`-style path into its hardened SLIP-0010 indices.
+fn parse_derivation_path(path: &str) -> Result<Vec<u32>> {
+    let rest = path.strip_prefix("m/").unwrap_or(path);
+    if rest.is_empty() {
+        return Ok(Vec::new());
+    }
+    rest.split('/').map(parse_hardened_index).collect()
+}
+
+/// Derive a Dilithium5 keypair at `path` (e.g. `m/44'/0'/0'/0/5'`) from a
+/// mnemonic, SLIP-0010 style. Since Dilithium5 isn't an elliptic-curve scheme,
+/// there's no public-key-only child derivation to exploit, so the whole tree
+/// is built the same way SLIP-0010's Ed25519 variant is: starting from
+/// `I = HMAC-SHA512(key = "BTPC-DILITHIUM5-seed", data = seed)`, split into a
+/// 32-byte child-seed `I_L` and a 32-byte chain code `I_R`; for each hardened
+/// path component `i`, compute
+/// `I = HMAC-SHA512(key = chain_code, data = 0x00 || I_L || ser32(i))` and
+/// recurse, always taking the left half as the next key seed and the right
+/// half as the next chain code. The leaf's `I_L` is fed into the same keygen
+/// `derive_dilithium5_keypair_from_mnemonic` uses. Deterministic: the same
+/// mnemonic + passphrase + path always yields the same keypair.
+pub fn derive_dilithium5_keypair_at_path(
+    mnemonic: &Mnemonic,
+    passphrase: Option<&str>,
+    path: &str,
+) -> Result<(Vec<u8>, SecretBox<Vec<u8>>, String)> {
+    let indices = parse_derivation_path(path)?;
+    let mut seed = mnemonic.to_seed(passphrase.unwrap_or(""));
+
+    let mut master = hmac_sha512(b"BTPC-DILITHIUM5-seed", &seed);
+    seed.zeroize();
+    let mut key_seed = master[0..32].to_vec();
+    let mut chain_code = master[32..64].to_vec();
+    master.zeroize();
+
+    for index in indices {
+        let mut data = Vec::with_capacity(1 + 32 + 4);
+        data.push(0x00);
+        data.extend_from_slice(&key_seed);
+        data.extend_from_slice(&index.to_be_bytes());
 
-    // Step 4: address = hex(SHA512(pk))
-    let digest = Sha512::digest(&pk);
-    let addr = hex::encode(digest);
+        let mut i = hmac_sha512(&chain_code, &data);
+        data.zeroize();
+        key_seed.zeroize();
+        chain_code.zeroize();
+        key_seed = i[0..32].to_vec();
+        chain_code = i[32..64].to_vec();
+        i.zeroize();
+    }
 
-    (pk, sk, addr)
+    let result = expand_dilithium5_keypair(&key_seed);
+    key_seed.zeroize();
+    chain_code.zeroize();
+    Ok(result)
 }
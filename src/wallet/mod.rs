@@ -0,0 +1,16 @@
+pub mod backend;
+pub mod backup;
+pub mod checkpoints;
+pub mod hd;
+pub mod info;
+pub mod key;
+pub mod keystore;
+pub mod lock;
+pub mod mnemonic;
+pub mod ops;
+pub mod paper;
+pub mod pdf;
+pub mod registry;
+pub mod scan;
+pub mod secret_manager;
+pub mod wallet_generator;
@@ -1,10 +1,11 @@
 //! Wallet operations
 
 use anyhow::{anyhow, Result};
-use base64::{engine::general_purpose::URL_SAFE, Engine};
+use base64::{engine::general_purpose, Engine};
 use bip39::Mnemonic;
 use colored::*;
 use rand::Rng;
+use secrecy::{ExposeSecret, SecretBox};
 use serde::{Deserialize, Serialize};
 use sha2::{Digest, Sha512};
 use std::fs;
@@ -24,6 +25,53 @@ pub struct Wallet {
     pub seed_phrase: String,
     #[serde(default = "default_derivation_path")]
     pub derivation_path: String,
+    /// Whether `encrypted_private_key`/`seed_phrase` hold genuine
+    /// XChaCha20-Poly1305 ciphertext (see `wallet::lock`) rather than
+    /// plaintext or the old irreversible hash placeholder.
+    #[serde(default)]
+    pub encrypted: bool,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub kdf: Option<crate::wallet::keystore::KdfParams>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub salt_b64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub nonce_b64: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub seed_nonce_b64: Option<String>,
+    /// Which `SigningBackend` owns this wallet's keypair. `Ledger` wallets
+    /// store no secret material in `encrypted_private_key` at all.
+    #[serde(default)]
+    pub backend: crate::wallet::backend::BackendKind,
+    /// Chain height at/after which this wallet's keys could have received
+    /// funds. `Cmd::Scan --rescan-from-birthday` starts from the checkpoint
+    /// at or below here instead of genesis. Defaults to 0 for wallets
+    /// created before this field existed.
+    #[serde(default)]
+    pub birthday_height: u64,
+    /// Height through which `Cmd::Scan` has already scanned, so an
+    /// interrupted rescan resumes instead of restarting from the birthday.
+    #[serde(default)]
+    pub last_scanned_height: u64,
+    /// The UTXO set found by the most recent `Cmd::Scan`, so a resumed scan
+    /// (`start_height = last_scanned_height + 1`) can seed from it instead
+    /// of losing every UTXO found before the resume point. `Cmd::Scan` sets
+    /// `balance` to `utxos.iter().map(|u| u.value).sum()`.
+    #[serde(default)]
+    pub utxos: Vec<crate::rpc::Utxo>,
+    /// Human-readable label set via `--alias` at `Init`/`Recover`, looked up
+    /// by `wallet::registry` so `--wallet <alias>` can resolve a name
+    /// instead of a file path.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub alias: Option<String>,
+    /// Network this wallet was created for (`testnet`, `mainnet`, ...),
+    /// shown by `Cmd::ListWallets`.
+    #[serde(default = "default_network")]
+    pub network: String,
+    /// Where this wallet's secret key is stored at rest, selected via
+    /// `--secret-manager` at `Init`/`Recover`. See
+    /// `wallet::secret_manager::SecretManagerKind`.
+    #[serde(default)]
+    pub secret_manager: crate::wallet::secret_manager::SecretManagerKind,
 }
 
 // Default value functions
@@ -42,63 +90,141 @@ fn default_seed_phrase() -> String {
 fn default_derivation_path() -> String {
     "m/44'/0'/0'/0/0".to_string()
 }
+fn default_network() -> String {
+    "testnet".to_string()
+}
+
+/// Bech32/Bech32m charset (shared with the vanity-address miner); excludes
+/// visually ambiguous chars.
+pub const BECH32_CHARSET: &[u8] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
 
 /// Wallet address generator using quantum-resistant algorithms
 mod address_generator {
     use super::*;
 
-    /// Generate a new quantum-resistant wallet address
-    pub fn generate_address(prefix: Option<&str>) -> String {
-        let prefix = prefix.unwrap_or("btpc");
-
-        // Generate random bytes for the address
-        let mut rng = rand::thread_rng();
-        let mut random_bytes = [0u8; 32];
-        rng.fill(&mut random_bytes);
+    use super::BECH32_CHARSET as CHARSET;
+    /// Constant XORed into the checksum polymod for Bech32m (BIP-350), as
+    /// opposed to the original Bech32 constant `1`.
+    const BECH32M_CONST: u32 = 0x2bc830a3;
+    const GEN: [u32; 5] = [
+        0x3b6a57b2, 0x26508e6d, 0x1ea119fa, 0x3d4233dd, 0x2a1462b3,
+    ];
+
+    fn polymod(values: &[u8]) -> u32 {
+        let mut chk: u32 = 1;
+        for &v in values {
+            let b = chk >> 25;
+            chk = ((chk & 0x1ffffff) << 5) ^ (v as u32);
+            for i in 0..5 {
+                if (b >> i) & 1 == 1 {
+                    chk ^= GEN[i];
+                }
+            }
+        }
+        chk
+    }
 
-        // Hash the random bytes with SHA-512
-        let mut hasher = Sha512::new();
-        hasher.update(random_bytes);
-        let hash_result = hasher.finalize();
+    fn hrp_expand(hrp: &str) -> Vec<u8> {
+        let mut v: Vec<u8> = hrp.bytes().map(|c| c >> 5).collect();
+        v.push(0);
+        v.extend(hrp.bytes().map(|c| c & 31));
+        v
+    }
 
-        // Encode in base64 URL-safe format using the new API
-        let encoded = URL_SAFE.encode(&hash_result);
+    /// Convert 8-bit bytes to 5-bit groups, big-endian, left-padding the
+    /// final group with zero bits.
+    fn to_5bit_groups(data: &[u8]) -> Vec<u8> {
+        let mut acc: u32 = 0;
+        let mut bits: u32 = 0;
+        let mut out = Vec::with_capacity((data.len() * 8 + 4) / 5);
+        for &b in data {
+            acc = (acc << 8) | b as u32;
+            bits += 8;
+            while bits >= 5 {
+                bits -= 5;
+                out.push(((acc >> bits) & 0x1f) as u8);
+            }
+        }
+        if bits > 0 {
+            out.push(((acc << (5 - bits)) & 0x1f) as u8);
+        }
+        out
+    }
 
-        // Format the address with prefix and checksum
-        format_address(prefix, &encoded)
+    fn create_checksum(hrp: &str, data: &[u8]) -> [u8; 6] {
+        let mut values = hrp_expand(hrp);
+        values.extend_from_slice(data);
+        values.extend_from_slice(&[0u8; 6]);
+        let poly = polymod(&values) ^ BECH32M_CONST;
+        let mut checksum = [0u8; 6];
+        for (i, c) in checksum.iter_mut().enumerate() {
+            *c = ((poly >> (5 * (5 - i))) & 31) as u8;
+        }
+        checksum
     }
 
-    /// Format the address with prefix and checksum
-    fn format_address(prefix: &str, encoded: &str) -> String {
-        // Take first 40 characters of the encoded string
-        let main_part = &encoded[..40.min(encoded.len())];
+    /// Bech32m-encode `payload` (e.g. a pubkey digest) under human-readable part `hrp`.
+    fn bech32m_encode(hrp: &str, payload: &[u8]) -> String {
+        let values = to_5bit_groups(payload);
+        let checksum = create_checksum(hrp, &values);
+        let mut out = String::with_capacity(hrp.len() + 1 + values.len() + checksum.len());
+        out.push_str(hrp);
+        out.push('1');
+        for v in values.iter().chain(checksum.iter()) {
+            out.push(CHARSET[*v as usize] as char);
+        }
+        out
+    }
 
-        // Simple checksum (last 4 chars of the hash)
-        let checksum = if encoded.len() >= 4 {
-            &encoded[encoded.len() - 4..]
-        } else {
-            encoded
-        };
+    /// Generate a new quantum-resistant wallet address: Bech32m over the
+    /// SHA-512 digest of a freshly generated Dilithium5 public key.
+    pub fn generate_address(prefix: Option<&str>) -> String {
+        let hrp = prefix.unwrap_or("btpc");
+        let kp = crate::wallet::key::generate_keypair();
+        encode_address(hrp, &kp.pk)
+    }
 
-        format!("{}:{}_{}", prefix, main_part, checksum)
+    /// Bech32m-encode the address for a given Dilithium5 public key.
+    pub fn encode_address(hrp: &str, pk: &[u8]) -> String {
+        let digest = Sha512::digest(pk);
+        bech32m_encode(hrp, &digest)
     }
 
-    /// Validate an address format
+    /// Validate an address's Bech32m checksum and HRP, catching corrupted characters.
     pub fn validate_address(address: &str) -> bool {
-        // Simple validation for demonstration
-        address.contains(':') && address.len() > 10 && address.len() < 100
+        let Some(sep) = address.rfind('1') else {
+            return false;
+        };
+        // hrp must be non-empty; data+checksum must hold at least the 6 checksum chars.
+        if sep == 0 || address.len() < sep + 1 + 6 {
+            return false;
+        }
+        let hrp = &address[..sep];
+        if !hrp.bytes().all(|c| (33..=126).contains(&c)) {
+            return false;
+        }
+        let data_part = &address[sep + 1..];
+        let mut values = Vec::with_capacity(data_part.len());
+        for c in data_part.chars() {
+            match CHARSET.iter().position(|&x| x as char == c) {
+                Some(v) => values.push(v as u8),
+                None => return false,
+            }
+        }
+        let mut check_input = hrp_expand(hrp);
+        check_input.extend_from_slice(&values);
+        polymod(&check_input) == BECH32M_CONST
     }
 
     /// Display address in a formatted way
     pub fn display_address(address: &str) {
-        let parts: Vec<&str> = address.split(':').collect();
-        if parts.len() != 2 {
+        let Some(sep) = address.rfind('1') else {
             println!("{}", "Invalid address format".red());
             return;
-        }
+        };
 
-        let prefix = parts[0];
-        let rest = parts[1];
+        let prefix = &address[..sep];
+        let rest = &address[sep + 1..];
 
         println!(
             "{}",
@@ -131,25 +257,16 @@ mod address_generator {
     }
 }
 
-/// Generate a proper public key (placeholder for real cryptographic implementation)
-fn generate_public_key(address: &str) -> String {
-    // In a real implementation, this would generate an actual cryptographic public key
-    // For now, we'll create a more realistic-looking placeholder
-    let mut hasher = Sha512::new();
-    hasher.update(address.as_bytes());
-    let hash = hasher.finalize();
-    format!("pk_{}", URL_SAFE.encode(&hash[..32])) // First 32 bytes of hash
+/// Base64-encode a Dilithium5 public key for storage in `Wallet::public_key`.
+fn encode_public_key(pk: &[u8]) -> String {
+    general_purpose::STANDARD.encode(pk)
 }
 
-/// Generate an encrypted private key (placeholder for real cryptographic implementation)
-fn generate_encrypted_private_key(address: &str, passphrase: &str) -> String {
-    // In a real implementation, this would use proper encryption
-    // For now, we'll create a more realistic-looking placeholder
-    let mut hasher = Sha512::new();
-    hasher.update(address.as_bytes());
-    hasher.update(passphrase.as_bytes());
-    let hash = hasher.finalize();
-    format!("enc_{}", URL_SAFE.encode(&hash[..48])) // First 48 bytes of hash
+/// Hex-encode a Dilithium5 secret key for storage in `Wallet::encrypted_private_key`
+/// while the wallet is not yet locked (see `wallet::lock`); this mirrors the
+/// format `lock::decrypt_wallet` writes back out after an explicit unlock.
+fn encode_plaintext_secret_key(sk: &[u8]) -> String {
+    crate::utils::hex_lower(sk)
 }
 
 /// Generate a new 24-word seed phrase
@@ -164,66 +281,203 @@ pub fn generate_seed_phrase() -> Result<String> {
     Ok(mnemonic.to_string())
 }
 
-/// Validate a seed phrase
+/// Validate a seed phrase. Routed through `mnemonic::import_mnemonic` so
+/// recovery rejects a bad word count or a failed checksum (e.g. a typo'd or
+/// transposed word) with the same strict validation the import/restore flow
+/// is tested against, instead of a looser ad hoc parse.
 pub fn validate_seed_phrase(phrase: &str) -> Result<()> {
-    Mnemonic::parse(phrase).map_err(|e| anyhow!("Invalid seed phrase: {}", e))?;
+    crate::wallet::mnemonic::import_mnemonic(phrase, bip39::Language::English)?;
     Ok(())
 }
 
+/// Deterministically derive the `(pk, sk, address)` of `wallet::hd`'s
+/// `(account 0, change 0, index)` child (the HMAC path
+/// `btpc/0'/0/{index}`, not a SLIP-0010 walk of `m/44'/0'/0'/0/{index}`)
+/// from a BIP-39 seed phrase: same phrase + passphrase + index always yields
+/// the same Dilithium5 keypair and address, unlike `address_generator`'s
+/// `rand::thread_rng()`-backed keygen.
+fn derive_keypair_at_index(
+    seed_phrase: &str,
+    passphrase: &str,
+    index: u32,
+) -> Result<(Vec<u8>, Vec<u8>, String)> {
+    let mnemonic = crate::wallet::mnemonic::import_mnemonic(seed_phrase, bip39::Language::English)?;
+    let seed = mnemonic.to_seed(passphrase);
+    let (pk, sk, _) = crate::wallet::hd::derive_account_address(&seed, 0, 0, index);
+    let address = crate::wallet::key::derive_address_from_pk(&pk);
+    Ok((pk, sk, address))
+}
+
+/// Derive a read-only `(public_key_base64, address)` pair at an arbitrary
+/// SLIP-0010-style hardened path (e.g. `m/44'/0'/0'/0/5'`) from a BIP-39 seed
+/// phrase, without touching the wallet file. Unlike `derive_keypair_at_index`
+/// (which walks the wallet's own sequential receive/change chain via
+/// `wallet::hd`), this exposes `wallet::mnemonic::derive_dilithium5_keypair_at_path`'s
+/// full path syntax directly, for auditing or watching an address outside
+/// that chain.
+pub fn derive_address_at_path(seed_phrase: &str, passphrase: &str, path: &str) -> Result<(String, String)> {
+    let mnemonic = crate::wallet::mnemonic::import_mnemonic(seed_phrase, bip39::Language::English)?;
+    let pass = if passphrase.is_empty() { None } else { Some(passphrase) };
+    let (pk, _sk, address) = crate::wallet::mnemonic::derive_dilithium5_keypair_at_path(&mnemonic, pass, path)?;
+    Ok((general_purpose::STANDARD.encode(&pk), address))
+}
+
+/// Derive the keypair + address at index 0 of the seed phrase's default
+/// derivation path.
+fn deterministic_keypair_from_phrase(
+    seed_phrase: &str,
+    passphrase: &str,
+) -> Result<(Vec<u8>, Vec<u8>, String)> {
+    derive_keypair_at_index(seed_phrase, passphrase, 0)
+}
+
+/// Parse the trailing index off a `m/44'/0'/0'/0/i`-style derivation path.
+fn derivation_path_index(path: &str) -> u32 {
+    path.rsplit('/').next().and_then(|s| s.parse().ok()).unwrap_or(0)
+}
+
 impl Wallet {
-    /// Create a new wallet
+    /// Create a new wallet, generating a real Dilithium5 keypair and
+    /// deriving its Bech32m address from the real public key.
     pub fn new() -> Self {
-        let address = address_generator::generate_address(Some("btpc"));
+        let kp = crate::wallet::key::generate_keypair();
+        let address = address_generator::encode_address("btpc", &kp.pk);
         Wallet {
-            address: address.clone(),
+            address,
             balance: 0,
-            public_key: generate_public_key(&address),
-            encrypted_private_key: generate_encrypted_private_key(&address, ""),
+            public_key: encode_public_key(&kp.pk),
+            encrypted_private_key: encode_plaintext_secret_key(&kp.sk),
             seed_phrase: String::new(),
             derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            encrypted: false,
+            kdf: None,
+            salt_b64: None,
+            nonce_b64: None,
+            seed_nonce_b64: None,
+            backend: crate::wallet::backend::BackendKind::Software,
+            birthday_height: 0,
+            last_scanned_height: 0,
+            utxos: Vec::new(),
+            alias: None,
+            network: default_network(),
+            secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
         }
     }
 
-    /// Create a new wallet with passphrase and network
-    pub fn new_with_passphrase(passphrase: &str, network: &str) -> Self {
-        let address = address_generator::generate_address(Some(network));
+    /// Create a new wallet with passphrase and network. `passphrase` is not
+    /// used to derive the keypair itself; call `wallet::lock::encrypt_wallet`
+    /// afterwards to lock the secret key at rest with it.
+    pub fn new_with_passphrase(_passphrase: &str, network: &str) -> Self {
+        let kp = crate::wallet::key::generate_keypair();
+        let address = address_generator::encode_address(network, &kp.pk);
         Wallet {
-            address: address.clone(),
+            address,
             balance: 0,
-            public_key: generate_public_key(&address),
-            encrypted_private_key: generate_encrypted_private_key(&address, passphrase),
+            public_key: encode_public_key(&kp.pk),
+            encrypted_private_key: encode_plaintext_secret_key(&kp.sk),
             seed_phrase: String::new(),
             derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            encrypted: false,
+            kdf: None,
+            salt_b64: None,
+            nonce_b64: None,
+            seed_nonce_b64: None,
+            backend: crate::wallet::backend::BackendKind::Software,
+            birthday_height: 0,
+            last_scanned_height: 0,
+            utxos: Vec::new(),
+            alias: None,
+            network: network.to_string(),
+            secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
         }
     }
 
-    /// Create a new wallet with seed phrase
+    /// Create a new wallet with seed phrase; its address and keypair are
+    /// deterministically derived from the seed so recovery reproduces them.
     pub fn new_with_seed() -> Result<Self> {
         let seed_phrase = generate_seed_phrase()?;
-        let address = address_generator::generate_address(Some("btpc"));
+        let (pk, sk, address) = deterministic_keypair_from_phrase(&seed_phrase, "")?;
 
         Ok(Wallet {
-            address: address.clone(),
+            address,
             balance: 0,
-            public_key: generate_public_key(&address),
-            encrypted_private_key: generate_encrypted_private_key(&address, ""),
+            public_key: encode_public_key(&pk),
+            encrypted_private_key: encode_plaintext_secret_key(&sk),
             seed_phrase,
             derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            encrypted: false,
+            kdf: None,
+            salt_b64: None,
+            nonce_b64: None,
+            seed_nonce_b64: None,
+            backend: crate::wallet::backend::BackendKind::Software,
+            birthday_height: 0,
+            last_scanned_height: 0,
+            utxos: Vec::new(),
+            alias: None,
+            network: default_network(),
+            secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
         })
     }
 
-    /// Create a new wallet with passphrase, network, and seed phrase
+    /// Create a new wallet with passphrase, network, and seed phrase.
+    /// `network` is stored as metadata (see `Wallet::network`); the
+    /// deterministic seed-derived address itself has no per-network HRP.
     pub fn new_with_passphrase_and_seed(passphrase: &str, network: &str) -> Result<Self> {
         let seed_phrase = generate_seed_phrase()?;
-        let address = address_generator::generate_address(Some(network));
+        let (pk, sk, address) = deterministic_keypair_from_phrase(&seed_phrase, passphrase)?;
 
         Ok(Wallet {
-            address: address.clone(),
+            address,
             balance: 0,
-            public_key: generate_public_key(&address),
-            encrypted_private_key: generate_encrypted_private_key(&address, passphrase),
+            public_key: encode_public_key(&pk),
+            encrypted_private_key: encode_plaintext_secret_key(&sk),
             seed_phrase,
             derivation_path: "m/44'/0'/0'/0/0".to_string(),
+            encrypted: false,
+            kdf: None,
+            salt_b64: None,
+            nonce_b64: None,
+            seed_nonce_b64: None,
+            backend: crate::wallet::backend::BackendKind::Software,
+            birthday_height: 0,
+            last_scanned_height: 0,
+            utxos: Vec::new(),
+            alias: None,
+            network: network.to_string(),
+            secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
+        })
+    }
+
+    /// Create a wallet backed by a connected Ledger device at `path`
+    /// (e.g. `m/44'/0'/0'/0/0`): the public key is fetched over APDU and no
+    /// secret material is ever stored locally.
+    #[cfg(feature = "ledger")]
+    pub fn new_with_ledger(path: &str, network: &str) -> Result<Self> {
+        use crate::wallet::backend::SigningBackend;
+        let backend = crate::wallet::backend::LedgerBackend::connect()?;
+        let pk = backend.get_public_key(path)?;
+        let address = address_generator::encode_address(network, &pk);
+
+        Ok(Wallet {
+            address,
+            balance: 0,
+            public_key: encode_public_key(&pk),
+            encrypted_private_key: String::new(),
+            seed_phrase: String::new(),
+            derivation_path: path.to_string(),
+            encrypted: false,
+            kdf: None,
+            salt_b64: None,
+            nonce_b64: None,
+            seed_nonce_b64: None,
+            backend: crate::wallet::backend::BackendKind::Ledger,
+            birthday_height: 0,
+            last_scanned_height: 0,
+            utxos: Vec::new(),
+            alias: None,
+            network: network.to_string(),
+            secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
         })
     }
 }
@@ -278,90 +532,227 @@ pub fn backup_wallet(wallet: &Wallet, backup_path: &Path) -> Result<()> {
     Ok(())
 }
 
-/// Recover wallet from seed phrase
-pub fn recover_wallet_from_seed(
+/// Build a full `Wallet` (including its real keypair) for an existing seed
+/// phrase at `derivation_path`'s index, using `mnemonic_passphrase` as the
+/// BIP-39 passphrase (distinct from any at-rest encryption passphrase).
+/// Shared by `recover_wallet_from_seed` and `wallet::backup::import_backup`.
+pub(crate) fn wallet_from_seed_phrase(
     seed_phrase: &str,
-    passphrase: &str,
-    network: &str,
-    path: &Path,
+    mnemonic_passphrase: &str,
+    derivation_path: &str,
 ) -> Result<Wallet> {
     validate_seed_phrase(seed_phrase)?;
-
-    // In a real implementation, you would derive keys from the seed phrase
-    // For this demo, we'll generate a new address but store the seed phrase
-    let address = address_generator::generate_address(Some(network));
-
-    let wallet = Wallet {
-        address: address.clone(),
+    let index = derivation_path_index(derivation_path);
+    let (pk, sk, address) = derive_keypair_at_index(seed_phrase, mnemonic_passphrase, index)?;
+    Ok(Wallet {
+        address,
         balance: 0,
-        public_key: generate_public_key(&address),
-        encrypted_private_key: generate_encrypted_private_key(&address, passphrase),
+        public_key: encode_public_key(&pk),
+        encrypted_private_key: encode_plaintext_secret_key(&sk),
         seed_phrase: seed_phrase.to_string(),
-        derivation_path: "m/44'/0'/0'/0/0".to_string(),
-    };
+        derivation_path: derivation_path.to_string(),
+        encrypted: false,
+        kdf: None,
+        salt_b64: None,
+        nonce_b64: None,
+        seed_nonce_b64: None,
+        backend: crate::wallet::backend::BackendKind::Software,
+        birthday_height: 0,
+        last_scanned_height: 0,
+        utxos: Vec::new(),
+        alias: None,
+        network: default_network(),
+        secret_manager: crate::wallet::secret_manager::SecretManagerKind::File,
+    })
+}
 
+/// Recover wallet from seed phrase.
+///
+/// The recovered address is deterministically derived from the seed phrase
+/// itself, so recovering the same phrase always yields the same address;
+/// `network` is stored as metadata only (see `Wallet::network`).
+pub fn recover_wallet_from_seed(
+    seed_phrase: &str,
+    passphrase: &str,
+    network: &str,
+    path: &Path,
+) -> Result<Wallet> {
+    let mut wallet = wallet_from_seed_phrase(seed_phrase, passphrase, "m/44'/0'/0'/0/0")?;
+    wallet.network = network.to_string();
     save_wallet(&wallet, path)?;
     Ok(wallet)
 }
 
-/// Generate a new address for the wallet with proper key regeneration
-pub fn generate_new_address(wallet: &mut Wallet, passphrase: &str) -> Result<()> {
+/// Generate a new address for the wallet, with a freshly generated Dilithium5
+/// keypair to match.
+pub fn generate_new_address(wallet: &mut Wallet, _passphrase: &str) -> Result<()> {
     println!("Generating new address...");
 
-    // Generate new address
-    let new_address = address_generator::generate_address(Some("btpc"));
+    let kp = crate::wallet::key::generate_keypair();
+    let new_address = address_generator::encode_address("btpc", &kp.pk);
 
-    // Generate new keys that match the new address
-    wallet.address = new_address.clone();
-    wallet.public_key = generate_public_key(&new_address);
-    wallet.encrypted_private_key = generate_encrypted_private_key(&new_address, passphrase);
+    wallet.address = new_address;
+    wallet.public_key = encode_public_key(&kp.pk);
+    wallet.encrypted_private_key = encode_plaintext_secret_key(&kp.sk);
 
     println!("New address generated successfully!");
     Ok(())
 }
 
-/// Generate a new address for the wallet with proper key regeneration from seed phrase
+/// Generate a new address for the wallet, deterministically derived from its
+/// seed phrase at the next index of `derivation_path`, so that repeating
+/// this call (or recovering the wallet) always reaches the same chain of
+/// addresses.
 pub fn generate_new_address_from_seed(wallet: &mut Wallet, passphrase: &str) -> Result<()> {
     println!("Generating new address from seed phrase...");
 
-    // In a real implementation, you would derive the new address from the seed phrase
-    // using the derivation path. For this demo, we'll generate a new address but
-    // maintain the same seed phrase.
-    let new_address = address_generator::generate_address(Some("btpc"));
-
-    // Generate new keys that match the new address
-    wallet.address = new_address.clone();
-    wallet.public_key = generate_public_key(&new_address);
-    wallet.encrypted_private_key = generate_encrypted_private_key(&new_address, passphrase);
-
-    // Increment derivation path for next address
-    if let Some(last_num) = wallet.derivation_path.split('/').last() {
-        if let Ok(num) = last_num.parse::<u32>() {
-            wallet.derivation_path = wallet
-                .derivation_path
-                .rsplitn(2, '/')
-                .last()
-                .unwrap_or("m/44'/0'/0'/0")
-                .to_string()
-                + "/"
-                + &(num + 1).to_string();
-        }
+    if wallet.seed_phrase.is_empty() {
+        return Err(anyhow!("wallet has no seed phrase to derive from"));
     }
 
+    let next_index = derivation_path_index(&wallet.derivation_path) + 1;
+    let (pk, sk, new_address) = derive_keypair_at_index(&wallet.seed_phrase, passphrase, next_index)?;
+
+    wallet.address = new_address;
+    wallet.public_key = encode_public_key(&pk);
+    wallet.encrypted_private_key = encode_plaintext_secret_key(&sk);
+
+    // Advance the derivation path to the index we just derived from.
+    let prefix = wallet
+        .derivation_path
+        .rsplitn(2, '/')
+        .last()
+        .unwrap_or("m/44'/0'/0'/0")
+        .to_string();
+    wallet.derivation_path = format!("{prefix}/{next_index}");
+
     println!("New address generated successfully!");
     Ok(())
 }
 
-/// Send funds from wallet
-pub fn send_funds(wallet: &mut Wallet, recipient: &str, amount: u64) -> Result<()> {
-    // Implementation for sending funds
-    if amount > wallet.balance {
+/// An unsigned wallet-level transfer: spend from the wallet's own address to
+/// one recipient. Distinct from `tx::model::Transaction`, which models full
+/// UTXO inputs/outputs for on-chain broadcast; this is the simple shape
+/// `send_funds` builds before handing off to `sign_transaction`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Transaction {
+    pub inputs: Vec<String>,
+    pub recipient: String,
+    pub amount: u64,
+    pub fee: u64,
+    pub nonce: u64,
+}
+
+impl Transaction {
+    /// Canonical bytes to sign/verify over.
+    pub fn signing_bytes(&self) -> Result<Vec<u8>> {
+        Ok(serde_json::to_vec(self)?)
+    }
+}
+
+/// A `Transaction` plus its Dilithium5 detached signature and signing public
+/// key, ready to serialize and broadcast.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SignedTransaction {
+    pub tx: Transaction,
+    pub public_key: String, // base64
+    pub signature: String,  // base64
+}
+
+/// Recover `wallet`'s real Dilithium5 secret key bytes: unlocks it with
+/// `passphrase` if encrypted, otherwise parses the plaintext hex already
+/// held in `encrypted_private_key`.
+pub fn wallet_secret_key_bytes(wallet: &Wallet, passphrase: &str) -> Result<Vec<u8>> {
+    if wallet.encrypted {
+        let password: SecretBox<String> = SecretBox::new(Box::new(passphrase.to_string()));
+        let unlocked = crate::wallet::lock::unlock_wallet(wallet, &password, None)?;
+        Ok(unlocked.secret_key.expose_secret().clone())
+    } else {
+        hex::decode(&wallet.encrypted_private_key)
+            .map_err(|e| anyhow!("wallet secret key is not valid hex: {e}"))
+    }
+}
+
+/// Sign `tx` with `wallet`'s keypair, routed through its `backend`: the
+/// `Software` backend unlocks `encrypted_private_key` with `passphrase` (if
+/// the wallet is encrypted) and signs in-process; the `Ledger` backend sends
+/// the transaction to a connected hardware device over APDU instead, never
+/// touching a locally stored secret key.
+pub fn sign_transaction(wallet: &Wallet, tx: &Transaction, passphrase: &str) -> Result<SignedTransaction> {
+    use crate::wallet::backend::BackendKind;
+
+    let tx_bytes = tx.signing_bytes()?;
+    let (public_key, signature) = match wallet.backend {
+        BackendKind::Software => {
+            let sk_bytes = wallet_secret_key_bytes(wallet, passphrase)?;
+            let sk_secret: SecretBox<Vec<u8>> = SecretBox::new(Box::new(sk_bytes));
+            let signature = crate::tx::signer::sign_tx(&sk_secret, &tx_bytes)?;
+            (wallet.public_key.clone(), signature)
+        }
+        BackendKind::Ledger => {
+            #[cfg(feature = "ledger")]
+            {
+                use crate::wallet::backend::SigningBackend;
+                let backend = crate::wallet::backend::LedgerBackend::connect()?;
+                let pk = backend.get_public_key(&wallet.derivation_path)?;
+                let sig = backend.sign(&wallet.derivation_path, &tx_bytes)?;
+                (
+                    general_purpose::STANDARD.encode(pk),
+                    general_purpose::STANDARD.encode(sig),
+                )
+            }
+            #[cfg(not(feature = "ledger"))]
+            {
+                return Err(anyhow!(
+                    "wallet uses the ledger backend but this build was compiled without the `ledger` feature"
+                ));
+            }
+        }
+    };
+
+    Ok(SignedTransaction {
+        tx: tx.clone(),
+        public_key,
+        signature,
+    })
+}
+
+/// Verify a `SignedTransaction` against the public key it carries.
+pub fn verify_transaction(signed: &SignedTransaction) -> Result<bool> {
+    let pk_bytes = general_purpose::STANDARD.decode(&signed.public_key)?;
+    let tx_bytes = signed.tx.signing_bytes()?;
+    crate::tx::signer::verify_tx(&pk_bytes, &tx_bytes, &signed.signature)
+}
+
+/// Build, sign, and return a broadcastable transaction spending `amount` (plus
+/// `fee`) from `wallet` to `recipient`. Does not touch `wallet.balance`
+/// directly — the wallet's real balance only moves once this signed
+/// transaction is confirmed on-chain.
+pub fn send_funds(
+    wallet: &Wallet,
+    recipient: &str,
+    amount: u64,
+    fee: u64,
+    nonce: u64,
+    passphrase: &str,
+) -> Result<SignedTransaction> {
+    let total = amount
+        .checked_add(fee)
+        .ok_or_else(|| anyhow!("amount + fee overflow"))?;
+    if total > wallet.balance {
         return Err(anyhow!("Insufficient funds"));
     }
 
+    let tx = Transaction {
+        inputs: vec![wallet.address.clone()],
+        recipient: recipient.to_string(),
+        amount,
+        fee,
+        nonce,
+    };
+
     println!("Sending {} units to {}", amount, recipient);
-    wallet.balance -= amount;
-    Ok(())
+    sign_transaction(wallet, &tx, passphrase)
 }
 
 /// Get wallet balance
@@ -413,6 +804,11 @@ pub fn validate_address(address: &str) -> bool {
     address_generator::validate_address(address)
 }
 
+/// Bech32m-encode the address for a given Dilithium5 public key under `hrp`.
+pub fn encode_address(hrp: &str, pk: &[u8]) -> String {
+    address_generator::encode_address(hrp, pk)
+}
+
 /// Display seed phrase in a secure way
 pub fn display_seed_phrase(seed_phrase: &str) {
     println!();
@@ -0,0 +1,142 @@
+//! Offline paper-wallet generator: in one call, produce a fresh Dilithium5
+//! keypair (or N of them) plus its mnemonic, Bech32m address, and encrypted
+//! keystore material, formatted for printing and air-gapped QR scanning.
+
+use base64::{engine::general_purpose, Engine as _};
+use qrcode::{Color as QrColor, QrCode};
+use std::path::Path;
+
+use crate::wallet::keystore::{default_kdf_params, encrypt_sk, KdfParams, Password};
+use crate::wallet::mnemonic::generate_mnemonic_24;
+use crate::wallet::ops::{encode_address, Wallet};
+use crate::wallet::pdf::{render_paper_wallet_pdf, PaperWalletPage};
+
+/// Max base64 chars per QR chunk; Dilithium5 secret keys (~4.9 KB) are far
+/// too large for a single scannable QR code.
+const SK_QR_CHUNK_LEN: usize = 200;
+
+/// Everything needed to print and later restore one cold-storage address.
+pub struct PaperWallet {
+    pub address: String,
+    /// Raw payload to encode as the address-side QR code.
+    pub address_qr_payload: String,
+    pub mnemonic: String,
+    /// Base64 ciphertext of the encrypted secret key, with the cipher's auth
+    /// tag appended; the nonce is in `nonce_b64`.
+    pub encrypted_secret_key_b64: String,
+    pub salt_b64: String,
+    pub nonce_b64: String,
+    pub kdf: KdfParams,
+    /// Secret-key ciphertext split into reassemblable QR payloads, each
+    /// prefixed `i/N:` so a scanner app can order and join them.
+    pub secret_key_qr_chunks: Vec<String>,
+}
+
+/// Generate `count` fresh paper wallets for `network`, each encrypted with `passphrase`.
+pub fn generate_paper_wallet(
+    count: usize,
+    passphrase: &Password,
+    network: &str,
+) -> anyhow::Result<Vec<PaperWallet>> {
+    (0..count.max(1))
+        .map(|_| generate_one(passphrase, network))
+        .collect()
+}
+
+fn generate_one(passphrase: &Password, network: &str) -> anyhow::Result<PaperWallet> {
+    // The keypair is `wallet::hd`'s (0, 0, 0) child of the mnemonic's seed
+    // under an empty BIP-39 passphrase, so the printed words restore it.
+    let mnemonic_obj = generate_mnemonic_24();
+    let seed = mnemonic_obj.to_seed("");
+    let (pk, sk, _) = crate::wallet::hd::derive_account_address(&seed, 0, 0, 0);
+    let address = encode_address(network, &pk);
+    let mnemonic = mnemonic_obj.to_string();
+    let kdf = default_kdf_params();
+    let enc = encrypt_sk(passphrase, &sk)?;
+
+    let encrypted_secret_key_b64 = general_purpose::STANDARD.encode(&enc.ciphertext);
+    let secret_key_qr_chunks = chunk_for_qr(&encrypted_secret_key_b64);
+
+    Ok(PaperWallet {
+        address: address.clone(),
+        address_qr_payload: address,
+        mnemonic,
+        encrypted_secret_key_b64,
+        salt_b64: general_purpose::STANDARD.encode(&enc.salt),
+        nonce_b64: general_purpose::STANDARD.encode(enc.nonce),
+        kdf,
+        secret_key_qr_chunks,
+    })
+}
+
+/// Split `payload` into numbered `i/N:<chunk>` strings small enough to each
+/// fit in one scannable QR code.
+fn chunk_for_qr(payload: &str) -> Vec<String> {
+    let chars: Vec<char> = payload.chars().collect();
+    let chunks: Vec<&[char]> = chars.chunks(SK_QR_CHUNK_LEN).collect();
+    let total = chunks.len();
+    chunks
+        .iter()
+        .enumerate()
+        .map(|(i, c)| format!("{}/{}:{}", i + 1, total, c.iter().collect::<String>()))
+        .collect()
+}
+
+impl From<&PaperWallet> for PaperWalletPage {
+    fn from(w: &PaperWallet) -> Self {
+        PaperWalletPage {
+            address: w.address.clone(),
+            seed_phrase: Some(w.mnemonic.clone()),
+            secret_qr_chunks: w.secret_key_qr_chunks.clone(),
+        }
+    }
+}
+
+/// Export a single existing wallet as a printable paper-wallet PDF at `path`.
+/// When `encrypt` is true, `wallet` must already be locked (see
+/// `wallet::lock::encrypt_wallet`), and its stored ciphertext is what gets
+/// QR-encoded; when false, `encrypted_private_key` is QR-encoded as stored
+/// (plaintext secret-key hex for an unlocked wallet, ciphertext for a locked one).
+pub fn export_paper_wallet(wallet: &Wallet, path: &Path, encrypt: bool) -> anyhow::Result<()> {
+    if encrypt && !wallet.encrypted {
+        return Err(anyhow::anyhow!(
+            "wallet must be locked with wallet::lock::encrypt_wallet before an encrypted paper-wallet export"
+        ));
+    }
+    let page = PaperWalletPage {
+        address: wallet.address.clone(),
+        seed_phrase: if wallet.seed_phrase.is_empty() {
+            None
+        } else {
+            Some(wallet.seed_phrase.clone())
+        },
+        secret_qr_chunks: chunk_for_qr(&wallet.encrypted_private_key),
+    };
+    render_paper_wallet_pdf(&[page], path)
+}
+
+/// Render a PDF with one page per already-generated paper wallet, pairing
+/// with `generate_paper_wallet`'s `count` fresh addresses so a batch of
+/// cold-storage addresses can be printed in a single run.
+pub fn export_paper_wallets_pdf(wallets: &[PaperWallet], path: &Path) -> anyhow::Result<()> {
+    let pages: Vec<PaperWalletPage> = wallets.iter().map(PaperWalletPage::from).collect();
+    render_paper_wallet_pdf(&pages, path)
+}
+
+/// Render `data` as a scannable matrix of Unicode block characters, suitable
+/// for embedding in printed or terminal output.
+pub fn render_qr_matrix(data: &str) -> anyhow::Result<String> {
+    let code = QrCode::new(data).map_err(|e| anyhow::anyhow!("QR encode failed: {e}"))?;
+    let width = code.width();
+    let mut out = String::with_capacity((width + 1) * width);
+    for y in 0..width {
+        for x in 0..width {
+            out.push(match code[(x, y)] {
+                QrColor::Dark => '█',
+                QrColor::Light => ' ',
+            });
+        }
+        out.push('\n');
+    }
+    Ok(out)
+}
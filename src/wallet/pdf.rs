@@ -0,0 +1,113 @@
+//! Printable PDF rendering for paper wallets (see `wallet::paper`): one page
+//! per wallet with the address QR up top, the secret-key QR chunks below
+//! it, and — when present — the 24-word seed phrase laid out in the same
+//! numbered grid `ops::display_seed_phrase` prints to the terminal.
+
+use printpdf::{BuiltinFont, Image, ImageTransform, Mm, PdfDocument};
+use qrcode::QrCode;
+use std::fs::File;
+use std::io::BufWriter;
+use std::path::Path;
+
+const PAGE_WIDTH_MM: f64 = 210.0; // A4
+const PAGE_HEIGHT_MM: f64 = 297.0;
+const QR_SIZE_MM: f64 = 60.0;
+
+/// Everything one printed page needs, independent of whether the secret-key
+/// material behind it is encrypted.
+pub struct PaperWalletPage {
+    pub address: String,
+    pub seed_phrase: Option<String>,
+    /// Numbered `i/N:<chunk>` QR payloads for the secret key (see
+    /// `paper::chunk_for_qr`); usually more than one, since a Dilithium5
+    /// secret key is far too large for a single scannable QR code.
+    pub secret_qr_chunks: Vec<String>,
+}
+
+fn qr_image(data: &str) -> anyhow::Result<image::DynamicImage> {
+    let code = QrCode::new(data).map_err(|e| anyhow::anyhow!("QR encode failed: {e}"))?;
+    let img = code.render::<image::Luma<u8>>().module_dimensions(8, 8).build();
+    Ok(image::DynamicImage::ImageLuma8(img))
+}
+
+fn draw_qr(
+    layer: &printpdf::PdfLayerReference,
+    data: &str,
+    x_mm: f64,
+    y_mm: f64,
+) -> anyhow::Result<()> {
+    let dyn_img = qr_image(data)?;
+    let px = dyn_img.width() as f64;
+    let image = Image::from_dynamic_image(&dyn_img);
+    let scale = QR_SIZE_MM / (px * 25.4 / 300.0);
+    image.add_to_layer(
+        layer.clone(),
+        ImageTransform {
+            translate_x: Some(Mm(x_mm)),
+            translate_y: Some(Mm(y_mm)),
+            scale_x: Some(scale),
+            scale_y: Some(scale),
+            ..Default::default()
+        },
+    );
+    Ok(())
+}
+
+/// Render one PDF page per entry in `pages` to `path`.
+pub fn render_paper_wallet_pdf(pages: &[PaperWalletPage], path: &Path) -> anyhow::Result<()> {
+    let (doc, page1, layer1) = PdfDocument::new(
+        "BTPC Paper Wallet",
+        Mm(PAGE_WIDTH_MM),
+        Mm(PAGE_HEIGHT_MM),
+        "Layer 1",
+    );
+    let font = doc.add_builtin_font(BuiltinFont::Helvetica)?;
+    let font_bold = doc.add_builtin_font(BuiltinFont::HelveticaBold)?;
+
+    for (i, page) in pages.iter().enumerate() {
+        let (page_idx, layer_idx) = if i == 0 {
+            (page1, layer1)
+        } else {
+            doc.add_page(Mm(PAGE_WIDTH_MM), Mm(PAGE_HEIGHT_MM), "Layer 1")
+        };
+        let layer = doc.get_page(page_idx).get_layer(layer_idx);
+
+        layer.use_text("BTPC COLD STORAGE WALLET", 16.0, Mm(20.0), Mm(270.0), &font_bold);
+        layer.use_text(format!("Address: {}", page.address), 9.0, Mm(20.0), Mm(262.0), &font);
+        draw_qr(&layer, &page.address, 20.0, 195.0)?;
+
+        let mut y = 188.0;
+        layer.use_text("SECRET KEY (scan chunks in order):", 10.0, Mm(20.0), Mm(y), &font_bold);
+        y -= 8.0;
+        for chunk in &page.secret_qr_chunks {
+            draw_qr(&layer, chunk, 20.0, y - QR_SIZE_MM)?;
+            y -= QR_SIZE_MM + 6.0;
+        }
+
+        if let Some(seed) = &page.seed_phrase {
+            layer.use_text("SEED PHRASE (24 WORDS):", 10.0, Mm(20.0), Mm(y), &font_bold);
+            y -= 8.0;
+            let words: Vec<&str> = seed.split_whitespace().collect();
+            for row in (0..words.len()).step_by(4) {
+                let line = format!(
+                    "{:2}.{:<12} {:2}.{:<12} {:2}.{:<12} {:2}.{:<12}",
+                    row + 1,
+                    words.get(row).unwrap_or(&""),
+                    row + 2,
+                    words.get(row + 1).unwrap_or(&""),
+                    row + 3,
+                    words.get(row + 2).unwrap_or(&""),
+                    row + 4,
+                    words.get(row + 3).unwrap_or(&""),
+                );
+                layer.use_text(line, 8.0, Mm(20.0), Mm(y), &font);
+                y -= 5.0;
+            }
+        }
+    }
+
+    let file = File::create(path)?;
+    doc.save(&mut BufWriter::new(file))
+        .map_err(|e| anyhow::anyhow!("failed to save paper wallet PDF: {e}"))?;
+    Ok(())
+}
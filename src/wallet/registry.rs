@@ -0,0 +1,96 @@
+//! Alias registry and wallet-directory scanning, so `--wallet <alias>` can
+//! resolve a human-readable name to a path and `Cmd::ListWallets` can show
+//! every wallet it knows about.
+//!
+//! The registry itself (alias -> path) lives alongside `config.json` in the
+//! config directory; it's just a lookup table, not a source of truth --
+//! `ListWallets { dir }` scans a directory directly instead of trusting it.
+
+use crate::wallet::ops::Wallet;
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::path::{Path, PathBuf};
+
+fn registry_path() -> PathBuf {
+    crate::config::get_config_path()
+        .parent()
+        .map(|dir| dir.join("wallets.json"))
+        .unwrap_or_else(|| PathBuf::from("wallets.json"))
+}
+
+fn load_registry() -> anyhow::Result<BTreeMap<String, String>> {
+    let path = registry_path();
+    if !path.exists() {
+        return Ok(BTreeMap::new());
+    }
+    let data = std::fs::read_to_string(path)?;
+    Ok(serde_json::from_str(&data)?)
+}
+
+fn save_registry(registry: &BTreeMap<String, String>) -> anyhow::Result<()> {
+    let path = registry_path();
+    if let Some(parent) = path.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(path, serde_json::to_string_pretty(registry)?)?;
+    Ok(())
+}
+
+/// Record `alias` -> `wallet_path`, so a later `--wallet <alias>` resolves
+/// back to this file.
+pub fn register(alias: &str, wallet_path: &Path) -> anyhow::Result<()> {
+    let mut registry = load_registry()?;
+    registry.insert(alias.to_string(), wallet_path.display().to_string());
+    save_registry(&registry)
+}
+
+/// Resolve `--wallet <alias-or-path>`: an alias known to the registry wins;
+/// anything else is treated as a literal path, unchanged.
+pub fn resolve_wallet_path(alias_or_path: &str) -> PathBuf {
+    match load_registry() {
+        Ok(registry) => match registry.get(alias_or_path) {
+            Some(path) => PathBuf::from(path),
+            None => PathBuf::from(alias_or_path),
+        },
+        Err(_) => PathBuf::from(alias_or_path),
+    }
+}
+
+/// Summary of one wallet file, as shown by `Cmd::ListWallets`.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WalletSummary {
+    pub path: String,
+    pub alias: Option<String>,
+    pub address: String,
+    pub network: String,
+}
+
+/// Scan `dir` (non-recursively) for `*.json` files that parse as a
+/// `Wallet`, skipping anything else (e.g. `config.json`, `wallets.json`).
+pub fn scan_wallets_dir(dir: &Path) -> anyhow::Result<Vec<WalletSummary>> {
+    let mut wallets = Vec::new();
+    let entries = std::fs::read_dir(dir)?;
+    for entry in entries {
+        let entry = entry?;
+        let path = entry.path();
+        if path.extension().and_then(|e| e.to_str()) != Some("json") {
+            continue;
+        }
+        let Ok(data) = std::fs::read_to_string(&path) else {
+            continue;
+        };
+        let Ok(wallet) = serde_json::from_str::<Wallet>(&data) else {
+            continue;
+        };
+        if wallet.address.is_empty() {
+            continue;
+        }
+        wallets.push(WalletSummary {
+            path: path.display().to_string(),
+            alias: wallet.alias,
+            address: wallet.address,
+            network: wallet.network,
+        });
+    }
+    Ok(wallets)
+}
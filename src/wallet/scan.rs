@@ -0,0 +1,194 @@
+//! Block-by-block chain rescan: walks the chain from a starting height,
+//! matching each block's outputs/spends against the wallet's address to
+//! rebuild its UTXO set. This is the fallback path for nodes that don't
+//! maintain an address index (unlike `sync::WalletSync`, which assumes one
+//! and simply asks the node for an address's UTXOs/history directly).
+//!
+//! Blocks are fetched ahead of the matcher by a small pool of worker
+//! threads, but matched strictly in height order, so `last_scanned_height`
+//! always reflects a contiguous prefix of the scanned range and an
+//! interrupted scan can safely resume from it.
+
+use crate::rpc::{Block, RpcClient, Utxo};
+use crate::wallet::checkpoints;
+use crate::wallet::ops::Wallet;
+use std::collections::HashMap;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::mpsc;
+
+/// How many blocks worker threads are allowed to fetch ahead of the matcher.
+const FETCH_AHEAD: usize = 8;
+/// Worker threads fetching blocks in parallel.
+const SCAN_WORKERS: usize = 4;
+/// Persist `last_scanned_height` to disk at most this often during a scan,
+/// so a very long scan doesn't hammer the wallet file but still resumes
+/// close to where it was interrupted.
+const PERSIST_EVERY: u64 = 50;
+
+pub struct ScanOptions {
+    /// Start scanning from exactly this height, overriding checkpoints and
+    /// any previously persisted progress.
+    pub from_height: Option<u64>,
+    /// Ignore `last_scanned_height` and restart from the checkpoint at or
+    /// below the wallet's birthday height.
+    pub rescan_from_birthday: bool,
+}
+
+pub struct ScanReport {
+    pub from_height: u64,
+    pub to_height: u64,
+    pub blocks_scanned: u64,
+    pub utxos_found: usize,
+    pub balance: u64,
+}
+
+/// Rescan `wallet` against `rpc`, updating its cached UTXO set, balance, and
+/// `last_scanned_height`. A resumed scan (`last_scanned_height > 0`) seeds
+/// from `wallet.utxos` instead of starting empty, so `balance` always
+/// reflects the full accumulated set rather than just the most recently
+/// scanned range. `persist` is called after every `PERSIST_EVERY` blocks and
+/// once more at the end, so the caller can flush `wallet.json` without
+/// `wallet::scan` needing to know where that file lives.
+pub fn scan_wallet(
+    wallet: &mut Wallet,
+    rpc: &RpcClient,
+    network: &str,
+    opts: ScanOptions,
+    mut on_progress: impl FnMut(u64, u64),
+    mut persist: impl FnMut(&Wallet) -> anyhow::Result<()>,
+) -> anyhow::Result<ScanReport> {
+    let tip = rpc.get_block_height()?;
+
+    // Prefer a real recorded checkpoint at or below the birthday height; if
+    // none exists yet (see `wallet::checkpoints` module docs), fall back to
+    // the birthday height itself rather than genesis.
+    let checkpoint_or_birthday = || {
+        checkpoints::nearest_checkpoint(network, wallet.birthday_height)
+            .map(|c| c.height)
+            .unwrap_or(wallet.birthday_height)
+    };
+
+    let start_height = if let Some(h) = opts.from_height {
+        h
+    } else if opts.rescan_from_birthday {
+        checkpoint_or_birthday()
+    } else if wallet.last_scanned_height > 0 {
+        wallet.last_scanned_height + 1
+    } else {
+        checkpoint_or_birthday()
+    };
+
+    // Seed from the UTXO set found by the previous scan so a resumed scan
+    // only needs to apply the new range's spends/outputs on top of it,
+    // instead of discarding everything found before the resume point.
+    let mut utxos: HashMap<(String, u32), Utxo> = wallet
+        .utxos
+        .iter()
+        .map(|u| ((u.txid.clone(), u.vout), u.clone()))
+        .collect();
+    let mut blocks_scanned = 0u64;
+
+    if start_height <= tip {
+        scan_range(rpc, start_height, tip, |block| {
+            apply_block(&mut utxos, wallet, &block);
+            blocks_scanned += 1;
+            wallet.last_scanned_height = block.height;
+            on_progress(block.height, tip);
+            if blocks_scanned % PERSIST_EVERY == 0 {
+                wallet.utxos = utxos.values().cloned().collect();
+                wallet.balance = utxos.values().map(|u| u.value).sum();
+                persist(wallet)?;
+            }
+            Ok(())
+        })?;
+    }
+
+    wallet.utxos = utxos.values().cloned().collect();
+    wallet.balance = utxos.values().map(|u| u.value).sum();
+    persist(wallet)?;
+
+    Ok(ScanReport {
+        from_height: start_height,
+        to_height: tip,
+        blocks_scanned,
+        utxos_found: utxos.len(),
+        balance: wallet.balance,
+    })
+}
+
+/// Add/remove `block`'s outputs/spends touching `wallet.address` to `utxos`.
+fn apply_block(utxos: &mut HashMap<(String, u32), Utxo>, wallet: &Wallet, block: &Block) {
+    for spent in &block.spent {
+        utxos.remove(&(spent.prevout_txid.clone(), spent.prevout_vout));
+    }
+    for output in &block.outputs {
+        if output.address == wallet.address {
+            utxos.insert(
+                (output.txid.clone(), output.vout),
+                Utxo {
+                    txid: output.txid.clone(),
+                    vout: output.vout,
+                    value: output.value,
+                },
+            );
+        }
+    }
+}
+
+/// Fetch blocks `start..=end` using `SCAN_WORKERS` threads pulling from a
+/// shared height counter, reordering results so `on_block` always sees
+/// strictly increasing heights even though fetches complete out of order.
+fn scan_range(
+    rpc: &RpcClient,
+    start: u64,
+    end: u64,
+    mut on_block: impl FnMut(Block) -> anyhow::Result<()>,
+) -> anyhow::Result<()> {
+    let next_height = AtomicU64::new(start);
+    // Next height the matcher is waiting for.
+    let matched = AtomicU64::new(start);
+    let (tx, rx) = mpsc::sync_channel::<(u64, anyhow::Result<Block>)>(FETCH_AHEAD);
+
+    std::thread::scope(|scope| {
+        for _ in 0..SCAN_WORKERS {
+            let tx = tx.clone();
+            let next_height = &next_height;
+            let matched = &matched;
+            scope.spawn(move || loop {
+                let height = next_height.fetch_add(1, Ordering::SeqCst);
+                if height > end {
+                    break;
+                }
+                while height >= matched.load(Ordering::SeqCst) + FETCH_AHEAD as u64 {
+                    std::thread::sleep(std::time::Duration::from_millis(5));
+                }
+                let result = rpc.get_block(height);
+                if tx.send((height, result)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(tx);
+
+        let mut pending: HashMap<u64, anyhow::Result<Block>> = HashMap::new();
+        let mut want = start;
+        let mut first_error: Option<anyhow::Error> = None;
+        for (height, result) in rx {
+            pending.insert(height, result);
+            while let Some(result) = pending.remove(&want) {
+                if first_error.is_none() {
+                    match result.and_then(|block| on_block(block).map(|_| ())) {
+                        Ok(()) => {}
+                        Err(e) => first_error = Some(e),
+                    }
+                }
+                want += 1;
+                matched.store(want, Ordering::SeqCst);
+            }
+        }
+        match first_error {
+            Some(e) => Err(e),
+            None => Ok(()),
+        }
+    })
+}
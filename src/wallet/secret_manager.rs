@@ -0,0 +1,170 @@
+//! Pluggable secret-storage backends, selected at `Init`/`Recover` via
+//! `--secret-manager <file|stronghold|offline>`. This is orthogonal to
+//! `wallet::backend::BackendKind` (which decides *where signing runs* — in
+//! process vs. a connected Ledger): `SecretManagerKind` instead decides
+//! *where the secret key lives at rest* — inside `wallet.json` itself, in a
+//! separate encrypted vault file next to it, or nowhere at all (watch-only).
+
+use anyhow::{anyhow, Result};
+use base64::{engine::general_purpose, Engine as _};
+use chacha20poly1305::{
+    aead::{Aead, KeyInit},
+    Key, XChaCha20Poly1305, XNonce,
+};
+use secrecy::SecretBox;
+use serde::{Deserialize, Serialize};
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+
+use crate::wallet::keystore::{default_kdf_params, derive_key, KdfParams, Password};
+use crate::wallet::ops::Wallet;
+
+/// Which secret-storage backend a wallet was created with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize, Default)]
+pub enum SecretManagerKind {
+    /// The secret key lives (optionally Argon2id/XChaCha20-Poly1305
+    /// encrypted, via `wallet::lock`) in `encrypted_private_key` itself.
+    #[default]
+    File,
+    /// The secret key lives in a separate encrypted vault file beside the
+    /// wallet file, locked with its own passphrase independent of any
+    /// wallet-level passphrase.
+    Stronghold,
+    /// Watch-only: only the public key/address are stored. Balance,
+    /// history, and scanning all work; signing is deferred to an external
+    /// signer.
+    Offline,
+}
+
+impl FromStr for SecretManagerKind {
+    type Err = anyhow::Error;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s {
+            "file" => Ok(SecretManagerKind::File),
+            "stronghold" => Ok(SecretManagerKind::Stronghold),
+            "offline" => Ok(SecretManagerKind::Offline),
+            other => Err(anyhow!(
+                "unknown --secret-manager {other:?} (expected file, stronghold, or offline)"
+            )),
+        }
+    }
+}
+
+/// On-disk format of a Stronghold vault file: the wallet's secret key,
+/// encrypted under its own passphrase, independent of `wallet::lock`'s
+/// per-wallet encryption.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct StrongholdVault {
+    kdf: KdfParams,
+    salt_b64: String,
+    nonce_b64: String,
+    ciphertext_b64: String,
+}
+
+/// The vault file living beside `wallet_path`, e.g. `wallet.json.stronghold`.
+fn vault_path(wallet_path: &Path) -> PathBuf {
+    let mut name = wallet_path.as_os_str().to_os_string();
+    name.push(".stronghold");
+    PathBuf::from(name)
+}
+
+/// Encrypt `secret_key` under `vault_passphrase` into the vault file beside
+/// `wallet_path`, overwriting any vault already there.
+pub fn write_stronghold_vault(wallet_path: &Path, secret_key: &[u8], vault_passphrase: &Password) -> Result<()> {
+    let mut salt = [0u8; 16];
+    getrandom::getrandom(&mut salt)?;
+    let kdf = default_kdf_params();
+    let key_bytes = derive_key(vault_passphrase, &salt, kdf.m, kdf.t, kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+
+    let mut nonce = [0u8; 24];
+    getrandom::getrandom(&mut nonce)?;
+    let ciphertext = cipher
+        .encrypt(XNonce::from_slice(&nonce), secret_key)
+        .map_err(|e| anyhow!("encrypt stronghold vault: {e}"))?;
+
+    let vault = StrongholdVault {
+        kdf,
+        salt_b64: general_purpose::STANDARD.encode(salt),
+        nonce_b64: general_purpose::STANDARD.encode(nonce),
+        ciphertext_b64: general_purpose::STANDARD.encode(ciphertext),
+    };
+    std::fs::write(vault_path(wallet_path), serde_json::to_string_pretty(&vault)?)?;
+    Ok(())
+}
+
+/// Decrypt the vault file beside `wallet_path` with `vault_passphrase`.
+pub fn read_stronghold_vault(wallet_path: &Path, vault_passphrase: &Password) -> Result<SecretBox<Vec<u8>>> {
+    let data = std::fs::read_to_string(vault_path(wallet_path))
+        .map_err(|e| anyhow!("failed to read stronghold vault beside {wallet_path:?}: {e}"))?;
+    let vault: StrongholdVault = serde_json::from_str(&data)?;
+
+    let salt = general_purpose::STANDARD.decode(&vault.salt_b64)?;
+    let key_bytes = derive_key(vault_passphrase, &salt, vault.kdf.m, vault.kdf.t, vault.kdf.p)?;
+    let cipher = XChaCha20Poly1305::new(Key::from_slice(&key_bytes));
+    let nonce = general_purpose::STANDARD.decode(&vault.nonce_b64)?;
+    let ciphertext = general_purpose::STANDARD.decode(&vault.ciphertext_b64)?;
+    let secret_key = cipher
+        .decrypt(XNonce::from_slice(&nonce), ciphertext.as_slice())
+        .map_err(|_| anyhow!("wrong vault passphrase or corrupted stronghold vault"))?;
+    Ok(SecretBox::new(Box::new(secret_key)))
+}
+
+/// Return a clear error if `wallet` is watch-only (`Offline`), instead of
+/// letting a caller that needs the secret key proceed and fail confusingly.
+pub fn require_signing_capable(wallet: &Wallet) -> Result<()> {
+    if wallet.secret_manager == SecretManagerKind::Offline {
+        Err(anyhow!(
+            "wallet {:?} is watch-only (secret-manager = offline); this operation needs an external signer",
+            wallet.address
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+/// Unlock `wallet`'s real secret key, routed through its `secret_manager`:
+/// `File` unlocks `encrypted_private_key` in place (see `wallet::lock`),
+/// `Stronghold` reads the separate vault file beside `wallet_path` with the
+/// same `passphrase`, and `Offline` always fails with a clear error.
+pub fn unlock_secret_key(wallet: &Wallet, wallet_path: &Path, passphrase: &str) -> Result<SecretBox<Vec<u8>>> {
+    match wallet.secret_manager {
+        SecretManagerKind::File => {
+            let sk_bytes = crate::wallet::ops::wallet_secret_key_bytes(wallet, passphrase)?;
+            Ok(SecretBox::new(Box::new(sk_bytes)))
+        }
+        SecretManagerKind::Stronghold => {
+            let vault_passphrase: Password = SecretBox::new(Box::new(passphrase.to_string()));
+            read_stronghold_vault(wallet_path, &vault_passphrase)
+        }
+        SecretManagerKind::Offline => Err(anyhow!(
+            "wallet {:?} is watch-only (secret-manager = offline); this operation needs an external signer",
+            wallet.address
+        )),
+    }
+}
+
+/// Generate a fresh keypair for `wallet`, routing the new secret key through
+/// its `secret_manager`: `File` behaves exactly like
+/// `wallet::ops::generate_new_address`; `Stronghold` writes the new secret
+/// key to the vault file beside `wallet_path` instead, leaving
+/// `encrypted_private_key` empty; `Offline` wallets cannot generate a new
+/// address locally at all (there is no secret to generate one from).
+pub fn generate_new_address(wallet: &mut Wallet, wallet_path: &Path, passphrase: &str) -> Result<()> {
+    require_signing_capable(wallet)?;
+    match wallet.secret_manager {
+        SecretManagerKind::File => crate::wallet::ops::generate_new_address(wallet, passphrase),
+        SecretManagerKind::Stronghold => {
+            let kp = crate::wallet::key::generate_keypair();
+            let new_address = crate::wallet::ops::encode_address("btpc", &kp.pk);
+            let vault_passphrase: Password = SecretBox::new(Box::new(passphrase.to_string()));
+            write_stronghold_vault(wallet_path, &kp.sk, &vault_passphrase)?;
+            wallet.address = new_address;
+            wallet.public_key = general_purpose::STANDARD.encode(&kp.pk);
+            wallet.encrypted_private_key = String::new();
+            Ok(())
+        }
+        SecretManagerKind::Offline => unreachable!("require_signing_capable already rejected Offline"),
+    }
+}
@@ -0,0 +1,40 @@
+use btpc_wallet::wallet::backend::{MockBackend, SigningBackend};
+use btpc_wallet::wallet::key::generate_keypair;
+
+#[test]
+fn test_mock_backend_signature_verifies() {
+    let kp = generate_keypair();
+    let pk = kp.pk.clone();
+    let backend = MockBackend { keypair: kp };
+
+    let path = "m/44'/0'/0'/0/0";
+    let message = b"transfer 1 BTP";
+
+    assert_eq!(backend.get_public_key(path).unwrap(), pk);
+
+    let sig = backend.sign(path, message).unwrap();
+
+    use pqcrypto_dilithium::dilithium5::{DetachedSignature, PublicKey};
+    use pqcrypto_traits::sign::{DetachedSignature as _, PublicKey as _};
+
+    let mut signed_over = path
+        .split('/')
+        .skip(1)
+        .flat_map(|c| {
+            let hardened = c.ends_with('\'');
+            let n: u32 = c.trim_end_matches('\'').parse().unwrap_or(0);
+            let value = if hardened { n | 0x8000_0000 } else { n };
+            value.to_be_bytes()
+        })
+        .collect::<Vec<u8>>();
+    signed_over.extend_from_slice(message);
+
+    let dilithium_pk = PublicKey::from_bytes(&pk).unwrap();
+    let dilithium_sig = DetachedSignature::from_bytes(&sig).unwrap();
+    assert!(pqcrypto_dilithium::dilithium5::verify_detached_signature(
+        &dilithium_sig,
+        &signed_over,
+        &dilithium_pk
+    )
+    .is_ok());
+}
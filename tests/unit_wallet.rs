@@ -1,23 +1,39 @@
 use base64::{engine::general_purpose, Engine as _};
+use secrecy::{ExposeSecret, SecretBox};
 
+use btpc_wallet::tx::signer::{sign_tx, verify_tx};
 use btpc_wallet::wallet::keystore::{decrypt_sk, encrypt_sk};
+use btpc_wallet::wallet::ops::validate_address;
 use btpc_wallet::wallet::{key::*, mnemonic::*};
 
+fn test_password(s: &str) -> SecretBox<String> {
+    SecretBox::new(Box::new(s.to_string()))
+}
+
 #[test]
 fn test_address_derivation_len() {
     let kp = generate_keypair();
     let addr = derive_address_from_pk(&kp.pk);
-    assert_eq!(addr.len(), 128); // 64-byte digest in hex
+    assert!(validate_address(&addr), "address {addr} must be valid Bech32m");
+    // "btpc" + '1' + 103 data chars (512-bit SHA-512 digest) + 6 checksum chars.
+    assert_eq!(addr.len(), 114);
 }
 
 #[test]
 fn test_encrypt_decrypt_sk_roundtrip() {
     let kp = generate_keypair();
-    let enc = encrypt_sk("test-pass", &kp.sk).unwrap();
+    let enc = encrypt_sk(&test_password("test-pass"), &kp.sk).unwrap();
 
     // decrypt_sk expects the salt as a base64 string (&str), not raw bytes
     let salt_b64 = general_purpose::STANDARD.encode(&enc.salt);
-    let dec = decrypt_sk("test-pass", &salt_b64, &enc.nonce, &enc.ciphertext).unwrap();
+    #[allow(deprecated)]
+    let dec = decrypt_sk(
+        &test_password("test-pass"),
+        &salt_b64,
+        &enc.nonce,
+        &enc.ciphertext,
+    )
+    .unwrap();
 
     assert_eq!(dec, kp.sk);
 }
@@ -41,5 +57,91 @@ fn test_mnemonic_deterministic_keygen_reproducible() {
     assert_eq!(pk1, pk2);
     assert_eq!(sk1, sk2);
     assert_eq!(addr1, addr2);
-    assert_eq!(addr1.len(), 128);
+    assert!(validate_address(&addr1));
+}
+
+#[test]
+fn test_mnemonic_keygen_produces_genuine_signable_dilithium5_keys() {
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    let m = bip39::Mnemonic::parse_normalized(phrase).unwrap();
+
+    let (pk, sk, _addr) = derive_dilithium5_keypair_from_mnemonic(&m, Some("pass"));
+
+    // Real ML-DSA-87 (Dilithium5) encoding, not the old 32-byte HKDF-stub halves.
+    assert_eq!(pk.len(), 2592);
+
+    let tx_bytes = b"mnemonic-derived-roundtrip";
+    let sig = sign_tx(&sk, tx_bytes).unwrap();
+    assert!(verify_tx(&pk, tx_bytes, &sig).unwrap());
+}
+
+#[test]
+fn test_derive_dilithium5_keypair_at_path_deterministic() {
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    let m = bip39::Mnemonic::parse_normalized(phrase).unwrap();
+
+    let (pk1, sk1, addr1) =
+        derive_dilithium5_keypair_at_path(&m, Some("pass"), "m/44'/0'/0'/0/5'").unwrap();
+    let (pk2, sk2, addr2) =
+        derive_dilithium5_keypair_at_path(&m, Some("pass"), "m/44'/0'/0'/0/5'").unwrap();
+
+    assert_eq!(pk1, pk2);
+    assert_eq!(sk1.expose_secret(), sk2.expose_secret());
+    assert_eq!(addr1, addr2);
+    assert_eq!(pk1.len(), 2592);
+
+    // A different leaf index must derive a different keypair.
+    let (pk3, _, addr3) =
+        derive_dilithium5_keypair_at_path(&m, Some("pass"), "m/44'/0'/0'/0/6'").unwrap();
+    assert_ne!(pk1, pk3);
+    assert_ne!(addr1, addr3);
+}
+
+#[test]
+fn test_derive_dilithium5_keypair_at_path_rejects_non_hardened_component() {
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    let m = bip39::Mnemonic::parse_normalized(phrase).unwrap();
+
+    let err = derive_dilithium5_keypair_at_path(&m, None, "m/44'/0'/0'/0/5").unwrap_err();
+    assert!(err.to_string().contains("hardened"));
+}
+
+#[test]
+fn test_import_mnemonic_rejects_bad_checksum() {
+    // Same 24 words as the valid test phrase above, but with the last two
+    // words swapped -- still in the wordlist, still 24 words, but the
+    // trailing checksum no longer matches the entropy.
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict length delay";
+    let err = import_mnemonic(phrase, bip39::Language::English).unwrap_err();
+    assert!(matches!(err, WalletError::InvalidChecksum(_)));
+}
+
+#[test]
+fn test_import_mnemonic_rejects_wrong_word_count() {
+    let err = import_mnemonic("hamster diagram private", bip39::Language::English).unwrap_err();
+    assert!(matches!(err, WalletError::InvalidWordCount { got: 3 }));
+}
+
+#[test]
+fn test_validate_seed_phrase_routes_through_import_mnemonic() {
+    use btpc_wallet::wallet::ops::validate_seed_phrase;
+
+    let valid = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    assert!(validate_seed_phrase(valid).is_ok());
+
+    let bad_checksum = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict length delay";
+    assert!(validate_seed_phrase(bad_checksum).is_err());
+}
+
+#[test]
+fn test_ops_derive_address_at_path_matches_mnemonic_api() {
+    let phrase = "hamster diagram private dutch cause delay private meat slide toddler razor book happy fancy gospel tennis maple dilemma loan word shrug inflict delay length";
+    let m = bip39::Mnemonic::parse_normalized(phrase).unwrap();
+    let (pk, _sk, addr) = derive_dilithium5_keypair_at_path(&m, None, "m/44'/0'/0'/0/5'").unwrap();
+
+    let (pk_b64, addr_from_ops) =
+        btpc_wallet::wallet::ops::derive_address_at_path(phrase, "", "m/44'/0'/0'/0/5'").unwrap();
+
+    assert_eq!(pk_b64, general_purpose::STANDARD.encode(&pk));
+    assert_eq!(addr_from_ops, addr);
 }